@@ -0,0 +1,592 @@
+use crate::ast::{AstNode, Operator};
+use crate::error::AigerError;
+use crate::symbol::Symbol;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::ops::Not;
+
+/// An edge of an [`Aig`]: a node, possibly complemented, numbered as in AIGER with
+/// `2 * var + complemented`. Variable 0 is the constant false.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AigLit(u32);
+
+impl AigLit {
+    pub const FALSE: AigLit = AigLit(0);
+    pub const TRUE: AigLit = AigLit(1);
+
+    pub fn var(&self) -> u32 {
+        self.0 >> 1
+    }
+
+    pub fn is_complemented(&self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    pub fn index(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Not for AigLit {
+    type Output = AigLit;
+
+    fn not(self) -> AigLit {
+        AigLit(self.0 ^ 1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AigNode {
+    False,
+    Input(Symbol),
+    And(AigLit, AigLit),
+}
+
+/// And-Inverter Graph: two-input AND gates connected by possibly complemented edges,
+/// with named inputs and outputs.
+///
+/// Gates are structurally hashed and trivial ones (`x ∧ x`, `x ∧ ¬x`, `x ∧ 1`, ...) are
+/// never created, so building the same function twice yields the same edge.
+#[derive(Debug, Clone)]
+pub struct Aig {
+    nodes: Vec<AigNode>,
+    inputs: Vec<u32>,
+    input_vars: HashMap<Symbol, u32>,
+    outputs: Vec<(Symbol, AigLit)>,
+    strash: HashMap<(AigLit, AigLit), AigLit>,
+}
+
+impl Default for Aig {
+    fn default() -> Self {
+        Aig::new()
+    }
+}
+
+impl Aig {
+    pub fn new() -> Aig {
+        Aig {
+            nodes: vec![AigNode::False],
+            inputs: Vec::new(),
+            input_vars: HashMap::new(),
+            outputs: Vec::new(),
+            strash: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, node: AigNode) -> AigLit {
+        self.nodes.push(node);
+        AigLit((self.nodes.len() as u32 - 1) << 1)
+    }
+
+    /// Edge of the input called `name`, created on first use.
+    pub fn input(&mut self, name: Symbol) -> AigLit {
+        if let Some(&var) = self.input_vars.get(&name) {
+            return AigLit(var << 1);
+        }
+
+        let lit = self.push(AigNode::Input(name));
+        self.inputs.push(lit.var());
+        self.input_vars.insert(name, lit.var());
+        lit
+    }
+
+    pub fn and(&mut self, a: AigLit, b: AigLit) -> AigLit {
+        // The larger edge comes first, as AIGER requires
+        let (a, b) = if a < b { (b, a) } else { (a, b) };
+        if b == AigLit::FALSE || a == !b {
+            return AigLit::FALSE;
+        }
+        if b == AigLit::TRUE || a == b {
+            return a;
+        }
+        if let Some(&lit) = self.strash.get(&(a, b)) {
+            return lit;
+        }
+
+        let lit = self.push(AigNode::And(a, b));
+        self.strash.insert((a, b), lit);
+        lit
+    }
+
+    pub fn or(&mut self, a: AigLit, b: AigLit) -> AigLit {
+        !self.and(!a, !b)
+    }
+
+    pub fn xor(&mut self, a: AigLit, b: AigLit) -> AigLit {
+        let left = self.and(a, !b);
+        let right = self.and(!a, b);
+        self.or(left, right)
+    }
+
+    pub fn apply(&mut self, op: &Operator, a: AigLit, b: AigLit) -> AigLit {
+        match op {
+            Operator::And => self.and(a, b),
+            Operator::Or => self.or(a, b),
+            Operator::Xor => self.xor(a, b),
+            Operator::Iff => !self.xor(a, b),
+            Operator::Implies => self.or(!a, b),
+        }
+    }
+
+    pub fn add_output(&mut self, name: Symbol, lit: AigLit) {
+        self.outputs.push((name, lit));
+    }
+
+    /// Names of the inputs, in creation order.
+    pub fn inputs(&self) -> Vec<Symbol> {
+        self.inputs
+            .iter()
+            .map(|&var| match self.nodes[var as usize] {
+                AigNode::Input(name) => name,
+                _ => unreachable!("Input list only holds inputs"),
+            })
+            .collect()
+    }
+
+    pub fn outputs(&self) -> &[(Symbol, AigLit)] {
+        &self.outputs
+    }
+
+    pub fn num_ands(&self) -> usize {
+        self.nodes.len() - 1 - self.inputs.len()
+    }
+
+    /// Value of every output, `None` when an input has no value.
+    pub fn evaluate(&self, values: &HashMap<Symbol, bool>) -> Option<Vec<bool>> {
+        // Nodes are created after their fanins, so one pass in order suffices
+        let mut node_values = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let value = match node {
+                AigNode::False => false,
+                AigNode::Input(name) => *values.get(name)?,
+                AigNode::And(a, b) => {
+                    let edge =
+                        |lit: &AigLit| node_values[lit.var() as usize] != lit.is_complemented();
+                    edge(a) && edge(b)
+                }
+            };
+            node_values.push(value);
+        }
+
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|(_, lit)| node_values[lit.var() as usize] != lit.is_complemented())
+            .collect();
+        Some(outputs)
+    }
+
+    /// The function of an edge as a formula of ANDs and negations.
+    pub fn to_ast(&self, lit: AigLit) -> AstNode {
+        let mut memo: HashMap<u32, AstNode> = HashMap::new();
+        let mut stack = vec![lit.var()];
+        while let Some(&var) = stack.last() {
+            if memo.contains_key(&var) {
+                stack.pop();
+                continue;
+            }
+            let ast = match &self.nodes[var as usize] {
+                AigNode::False => AstNode::Constant(false),
+                AigNode::Input(name) => AstNode::Variable(*name),
+                AigNode::And(a, b) => {
+                    let pending: Vec<u32> = [a.var(), b.var()]
+                        .into_iter()
+                        .filter(|v| !memo.contains_key(v))
+                        .collect();
+                    if !pending.is_empty() {
+                        stack.extend(pending);
+                        continue;
+                    }
+                    let edge = |lit: &AigLit| {
+                        let node = memo[&lit.var()].clone();
+                        if lit.is_complemented() {
+                            AstNode::Not(Box::new(node))
+                        } else {
+                            node
+                        }
+                    };
+                    AstNode::BinaryOperator(Operator::And, Box::new(edge(a)), Box::new(edge(b)))
+                }
+            };
+            memo.insert(var, ast);
+            stack.pop();
+        }
+
+        let node = memo
+            .remove(&lit.var())
+            .expect("Every node on the stack is converted");
+        match (node, lit.is_complemented()) {
+            (AstNode::Constant(value), true) => AstNode::Constant(!value),
+            (node, true) => AstNode::Not(Box::new(node)),
+            (node, false) => node,
+        }
+    }
+
+    // AIGER numbering: inputs first, then the gates in creation order
+    fn numbering(&self) -> Vec<u32> {
+        let mut numbers = vec![0; self.nodes.len()];
+        for (i, &var) in self.inputs.iter().enumerate() {
+            numbers[var as usize] = i as u32 + 1;
+        }
+        let mut next = self.inputs.len() as u32 + 1;
+        for (var, node) in self.nodes.iter().enumerate() {
+            if let AigNode::And(_, _) = node {
+                numbers[var] = next;
+                next += 1;
+            }
+        }
+        numbers
+    }
+
+    // Header, output lines and gates with their fanins ordered as AIGER requires
+    fn sections(&self) -> (Vec<u32>, Vec<(u32, u32, u32)>) {
+        let numbers = self.numbering();
+        let renumber =
+            |lit: &AigLit| numbers[lit.var() as usize] << 1 | lit.is_complemented() as u32;
+
+        let outputs = self.outputs.iter().map(|(_, lit)| renumber(lit)).collect();
+        let ands = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(var, node)| match node {
+                AigNode::And(a, b) => {
+                    let (a, b) = (renumber(a), renumber(b));
+                    Some((numbers[var] << 1, a.max(b), a.min(b)))
+                }
+                _ => None,
+            })
+            .collect();
+        (outputs, ands)
+    }
+
+    fn header(&self, format: &str) -> String {
+        let inputs = self.inputs.len();
+        format!(
+            "{} {} {} 0 {} {}\n",
+            format,
+            inputs + self.num_ands(),
+            inputs,
+            self.outputs.len(),
+            self.num_ands()
+        )
+    }
+
+    fn symbols(&self) -> String {
+        let mut symbols = String::new();
+        for (i, name) in self.inputs().iter().enumerate() {
+            writeln!(symbols, "i{} {}", i, name).unwrap();
+        }
+        for (i, (name, _)) in self.outputs.iter().enumerate() {
+            writeln!(symbols, "o{} {}", i, name).unwrap();
+        }
+        symbols
+    }
+
+    /// The graph in the ASCII AIGER format.
+    pub fn to_aag(&self) -> String {
+        let (outputs, ands) = self.sections();
+        let mut text = self.header("aag");
+        for i in 0..self.inputs.len() {
+            writeln!(text, "{}", (i + 1) * 2).unwrap();
+        }
+        for output in outputs {
+            writeln!(text, "{}", output).unwrap();
+        }
+        for (lhs, rhs0, rhs1) in ands {
+            writeln!(text, "{} {} {}", lhs, rhs0, rhs1).unwrap();
+        }
+        text + &self.symbols()
+    }
+
+    /// The graph in the binary AIGER format, where gates are delta-encoded.
+    pub fn to_aig(&self) -> Vec<u8> {
+        let (outputs, ands) = self.sections();
+        let mut bytes = self.header("aig").into_bytes();
+        for output in outputs {
+            bytes.extend(format!("{}\n", output).bytes());
+        }
+        for (lhs, rhs0, rhs1) in ands {
+            encode(&mut bytes, lhs - rhs0);
+            encode(&mut bytes, rhs0 - rhs1);
+        }
+        bytes.extend(self.symbols().bytes());
+        bytes
+    }
+
+    /// Read a combinational circuit in the ASCII (`aag`) or binary (`aig`) AIGER format.
+    /// Unnamed inputs and outputs are called `i<index>` and `o<index>`.
+    pub fn parse(input: &[u8]) -> Result<Aig, AigerError> {
+        let mut reader = Reader {
+            input,
+            pos: 0,
+            line: 0,
+        };
+
+        let header = reader.line().ok_or(AigerError::InvalidHeader)?;
+        let words: Vec<&str> = header.split_whitespace().collect();
+        let (binary, numbers) = match words.as_slice() {
+            ["aag", rest @ ..] if rest.len() == 5 => (false, rest),
+            ["aig", rest @ ..] if rest.len() == 5 => (true, rest),
+            _ => return Err(AigerError::InvalidHeader),
+        };
+        let numbers: Vec<u32> = numbers
+            .iter()
+            .map(|n| n.parse().map_err(|_| AigerError::InvalidHeader))
+            .collect::<Result<_, _>>()?;
+        let [max_var, num_inputs, num_latches, num_outputs, num_ands] = numbers[..] else {
+            return Err(AigerError::InvalidHeader);
+        };
+        if num_latches > 0 {
+            return Err(AigerError::Latches { count: num_latches });
+        }
+        // Every input and gate has its own variable, and every line or delta takes at least
+        // one byte, so that the counts are checked before anything is allocated. Binary
+        // inputs have no line and are only bounded by the maximum variable index.
+        if num_inputs as u64 + num_ands as u64 > max_var as u64 {
+            return Err(AigerError::InvalidHeader);
+        }
+        let input_lines = if binary { 0 } else { num_inputs as u64 };
+        let lines = input_lines + num_outputs as u64 + if binary { 2 } else { 1 } * num_ands as u64;
+        if lines > reader.remaining() as u64 {
+            return Err(AigerError::UnexpectedEnd);
+        }
+
+        let check = |literal: u32| {
+            if literal >> 1 > max_var {
+                Err(AigerError::UndefinedLiteral { literal })
+            } else {
+                Ok(literal)
+            }
+        };
+
+        // Variables of the ASCII inputs and gates, each defined once
+        let mut defined = HashSet::new();
+        let mut input_vars = Vec::with_capacity(num_inputs as usize);
+        for i in 0..num_inputs {
+            if binary {
+                input_vars.push(i + 1);
+            } else {
+                let [lit] = reader.numbers::<1>()?;
+                let lit = check(lit)?;
+                if lit & 1 == 1 || lit == 0 {
+                    return Err(AigerError::InvalidLine { line: reader.line });
+                }
+                if !defined.insert(lit >> 1) {
+                    return Err(AigerError::Redefined { literal: lit });
+                }
+                input_vars.push(lit >> 1);
+            }
+        }
+
+        let mut outputs = Vec::with_capacity(num_outputs as usize);
+        for _ in 0..num_outputs {
+            let [lit] = reader.numbers::<1>()?;
+            outputs.push(check(lit)?);
+        }
+
+        let mut gates: Vec<(u32, u32, u32)> = Vec::with_capacity(num_ands as usize);
+        for i in 0..num_ands {
+            if binary {
+                let lhs = (num_inputs + i + 1)
+                    .checked_mul(2)
+                    .ok_or(AigerError::Overflow)?;
+                let rhs0 = lhs
+                    .checked_sub(reader.varint()?)
+                    .ok_or(AigerError::UndefinedLiteral { literal: lhs })?;
+                let rhs1 = rhs0
+                    .checked_sub(reader.varint()?)
+                    .ok_or(AigerError::UndefinedLiteral { literal: rhs0 })?;
+                gates.push((lhs, rhs0, rhs1));
+            } else {
+                let [lhs, rhs0, rhs1] = reader.numbers::<3>()?;
+                if lhs & 1 == 1 || lhs == 0 {
+                    return Err(AigerError::InvalidLine { line: reader.line });
+                }
+                if !defined.insert(check(lhs)? >> 1) {
+                    return Err(AigerError::Redefined { literal: lhs });
+                }
+                gates.push((check(lhs)?, check(rhs0)?, check(rhs1)?));
+            }
+        }
+
+        // Symbol table, up to the comment section
+        let mut input_names = HashMap::new();
+        let mut output_names = HashMap::new();
+        while let Some(bytes) = reader.bytes() {
+            if bytes.starts_with(b"c") {
+                break;
+            }
+            // Symbol names are not required to be UTF-8, such lines are left out and the
+            // default name is used
+            let Ok(line) = std::str::from_utf8(bytes) else {
+                continue;
+            };
+            let invalid = AigerError::InvalidLine { line: reader.line };
+            let (position, name) = line.split_once(' ').ok_or(invalid.clone())?;
+            let mut chars = position.chars();
+            let kind = chars.next();
+            let index: usize = chars.as_str().parse().map_err(|_| invalid.clone())?;
+            match kind {
+                Some('i') => input_names.insert(index, Symbol::new(name)),
+                Some('o') => output_names.insert(index, Symbol::new(name)),
+                _ => return Err(invalid),
+            };
+        }
+
+        let mut aig = Aig::new();
+        let mut edges: HashMap<u32, AigLit> = HashMap::from([(0, AigLit::FALSE)]);
+        for (i, &var) in input_vars.iter().enumerate() {
+            let name = input_names
+                .get(&i)
+                .copied()
+                .unwrap_or_else(|| Symbol::new(&format!("i{}", i)));
+            edges.insert(var, aig.input(name));
+        }
+
+        let definitions: HashMap<u32, (u32, u32)> = gates
+            .iter()
+            .map(|&(lhs, rhs0, rhs1)| (lhs >> 1, (rhs0, rhs1)))
+            .collect();
+        // Gates of the ASCII format may come in any order, so they are built depth first
+        for &(lhs, _, _) in &gates {
+            let mut stack = vec![lhs >> 1];
+            let mut visiting = HashSet::new();
+            while let Some(&var) = stack.last() {
+                if edges.contains_key(&var) {
+                    stack.pop();
+                    continue;
+                }
+                let Some(&(rhs0, rhs1)) = definitions.get(&var) else {
+                    return Err(AigerError::UndefinedLiteral { literal: var << 1 });
+                };
+                let pending: Vec<u32> = [rhs0 >> 1, rhs1 >> 1]
+                    .into_iter()
+                    .filter(|v| !edges.contains_key(v))
+                    .collect();
+                if pending.is_empty() {
+                    let edge = |lit: u32| {
+                        let edge = edges[&(lit >> 1)];
+                        if lit & 1 == 1 {
+                            !edge
+                        } else {
+                            edge
+                        }
+                    };
+                    let lit = aig.and(edge(rhs0), edge(rhs1));
+                    edges.insert(var, lit);
+                    stack.pop();
+                } else if !visiting.insert(var) {
+                    return Err(AigerError::Cycle { literal: var << 1 });
+                } else {
+                    stack.extend(pending);
+                }
+            }
+        }
+
+        for (i, &lit) in outputs.iter().enumerate() {
+            let edge = *edges
+                .get(&(lit >> 1))
+                .ok_or(AigerError::UndefinedLiteral { literal: lit })?;
+            let edge = if lit & 1 == 1 { !edge } else { edge };
+            let name = output_names
+                .get(&i)
+                .copied()
+                .unwrap_or_else(|| Symbol::new(&format!("o{}", i)));
+            aig.add_output(name, edge);
+        }
+
+        Ok(aig)
+    }
+}
+
+// Unsigned integer in 7-bit groups, least significant first, the high bit marking that
+// more groups follow
+fn encode(bytes: &mut Vec<u8>, mut n: u32) {
+    while n >= 0x80 {
+        bytes.push((n & 0x7f) as u8 | 0x80);
+        n >>= 7;
+    }
+    bytes.push(n as u8);
+}
+
+struct Reader<'a> {
+    input: &'a [u8],
+    pos: usize,
+    // Number of the last line read
+    line: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn remaining(&self) -> usize {
+        self.input.len().saturating_sub(self.pos)
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        if self.pos >= self.input.len() {
+            return None;
+        }
+        let rest = &self.input[self.pos..];
+        let end = rest.iter().position(|&b| b == b'\n').unwrap_or(rest.len());
+        self.pos += end + 1;
+        self.line += 1;
+        Some(&rest[..end])
+    }
+
+    // A line that is not UTF-8 reads as empty, which no header or number line accepts
+    fn line(&mut self) -> Option<&'a str> {
+        self.bytes()
+            .map(|bytes| std::str::from_utf8(bytes).unwrap_or(""))
+    }
+
+    fn numbers<const N: usize>(&mut self) -> Result<[u32; N], AigerError> {
+        let line = self.line().ok_or(AigerError::UnexpectedEnd)?;
+        let invalid = AigerError::InvalidLine { line: self.line };
+        let numbers: Vec<u32> = line
+            .split_whitespace()
+            .map(|word| word.parse().map_err(|_| invalid.clone()))
+            .collect::<Result<_, _>>()?;
+        numbers.try_into().map_err(|_| invalid)
+    }
+
+    fn varint(&mut self) -> Result<u32, AigerError> {
+        let mut n: u32 = 0;
+        let mut shift = 0;
+        loop {
+            let byte = *self.input.get(self.pos).ok_or(AigerError::UnexpectedEnd)?;
+            self.pos += 1;
+            // Five groups hold 35 bits, of which only the low four of the last may be set
+            let group = (byte & 0x7f) as u32;
+            if shift >= 32 || (shift == 28 && group > 0x0f) {
+                return Err(AigerError::Overflow);
+            }
+            n |= group << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+            shift += 7;
+        }
+    }
+}
+
+impl AstNode {
+    /// Edge computing the formula in `aig`, adding its variables as inputs on first use.
+    pub fn to_aig(&self, aig: &mut Aig) -> AigLit {
+        match self {
+            AstNode::Constant(value) => {
+                if *value {
+                    AigLit::TRUE
+                } else {
+                    AigLit::FALSE
+                }
+            }
+            AstNode::Variable(name) => aig.input(*name),
+            AstNode::Not(child) => !child.to_aig(aig),
+            AstNode::BinaryOperator(op, left, right) => {
+                let left = left.to_aig(aig);
+                let right = right.to_aig(aig);
+                aig.apply(op, left, right)
+            }
+        }
+    }
+}
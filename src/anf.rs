@@ -0,0 +1,147 @@
+use crate::ast::{AstNode, Operator};
+use crate::error::Error;
+use crate::symbol::Symbol;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Algebraic normal form, or Zhegalkin polynomial: the XOR of AND-monomials that is
+/// unique for every function of the variables.
+///
+/// A monomial is the mask of its variables, the first variable being the most significant
+/// bit as for minterms; the empty monomial is the constant 1. Monomials are kept by
+/// increasing degree, then in variable order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anf {
+    variables: Vec<Symbol>,
+    monomials: Vec<u64>,
+}
+
+impl Anf {
+    /// Largest number of variables of a formula converted by [`AstNode::to_anf`], which
+    /// goes through its truth table.
+    pub const MAX_VARIABLES: usize = 20;
+
+    pub fn new(variables: Vec<Symbol>, mut monomials: Vec<u64>) -> Anf {
+        // Equal monomials cancel out
+        monomials.sort_by_key(|&m| (m.count_ones(), Reverse(m)));
+        let mut reduced: Vec<u64> = Vec::with_capacity(monomials.len());
+        for m in monomials {
+            if reduced.last() == Some(&m) {
+                reduced.pop();
+            } else {
+                reduced.push(m);
+            }
+        }
+
+        Anf {
+            variables,
+            monomials: reduced,
+        }
+    }
+
+    /// Polynomial of the function whose value on minterm `m` is `values[m]`, computed with
+    /// the Möbius transform. `values` must hold `2^n` entries for `n` variables, so `n` is
+    /// below the bit width of `usize`.
+    pub fn from_truth_table(variables: Vec<Symbol>, values: &[bool]) -> Result<Anf, Error> {
+        let n = variables.len();
+        let limit = usize::BITS as usize - 1;
+        if n > limit {
+            return Err(Error::TooManyVariables {
+                variables: n,
+                limit,
+            });
+        }
+        if values.len() != 1 << n {
+            return Err(Error::TableLength {
+                expected: 1 << n,
+                found: values.len(),
+            });
+        }
+
+        let mut coefficients = values.to_vec();
+        for bit in (0..n).map(|i| 1usize << i) {
+            for m in 0..coefficients.len() {
+                if m & bit != 0 {
+                    coefficients[m] ^= coefficients[m ^ bit];
+                }
+            }
+        }
+
+        let monomials = (0..coefficients.len() as u64)
+            .filter(|&m| coefficients[m as usize])
+            .collect();
+        Ok(Anf::new(variables, monomials))
+    }
+
+    pub fn variables(&self) -> &[Symbol] {
+        &self.variables
+    }
+
+    pub fn monomials(&self) -> &[u64] {
+        &self.monomials
+    }
+
+    /// Largest number of variables in a monomial, 0 for constant functions.
+    pub fn degree(&self) -> usize {
+        self.monomials
+            .iter()
+            .map(|m| m.count_ones() as usize)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn evaluate(&self, values: &HashMap<Symbol, bool>) -> Option<bool> {
+        let n = self.variables.len();
+        let mut point = 0u64;
+        for (i, name) in self.variables.iter().enumerate() {
+            if *values.get(name)? {
+                point |= 1 << (n - 1 - i);
+            }
+        }
+        // A monomial is true when all its variables are
+        Some(self.monomials.iter().filter(|&&m| m & point == m).count() % 2 == 1)
+    }
+
+    // Variables of a monomial in order
+    fn factors(&self, monomial: u64) -> Vec<AstNode> {
+        let n = self.variables.len();
+        self.variables
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| monomial >> (n - 1 - i) & 1 == 1)
+            .map(|(_, &name)| AstNode::Variable(name))
+            .collect()
+    }
+
+    /// The polynomial as a right-associated XOR of right-associated conjunctions.
+    pub fn to_ast(&self) -> AstNode {
+        let monomials = self
+            .monomials
+            .iter()
+            .map(|&m| AstNode::right_fold(self.factors(m), Operator::And, true))
+            .collect();
+        AstNode::right_fold(monomials, Operator::Xor, false)
+    }
+
+    pub fn to_rpn(&self) -> String {
+        self.to_ast().to_rpn()
+    }
+}
+
+impl AstNode {
+    /// Zhegalkin polynomial of the formula over its sorted variables, or an error if it has
+    /// more than [`Anf::MAX_VARIABLES`] of them.
+    pub fn to_anf(&self) -> Result<Anf, Error> {
+        let variables = self.get_variables().len();
+        if variables > Anf::MAX_VARIABLES {
+            return Err(Error::TooManyVariables {
+                variables,
+                limit: Anf::MAX_VARIABLES,
+            });
+        }
+
+        let table = self.to_truth_table();
+        let values: Vec<bool> = (0..table.rows()).map(|row| table.value(row)).collect();
+        Anf::from_truth_table(table.variables().to_vec(), &values)
+    }
+}
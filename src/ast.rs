@@ -1,11 +1,14 @@
-use std::collections::{HashMap, HashSet, LinkedList};
+use crate::error::{Error, ParseError};
+use crate::set_engine::{Bitset, SetEngine};
+use crate::symbol::Symbol;
+use std::collections::{BTreeSet, HashMap, HashSet, LinkedList};
 use std::fmt;
+use std::hash::Hash;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Operator {
     Or,      // Logical OR (∨)
     And,     // Logical AND (∧)
-    Not,     // Logical NOT (¬)
     Xor,     // Logical XOR (exclusive OR ⊕)
     Iff,     // Logical Equivalence (↔)
     Implies, // Logical Implication (→)
@@ -16,7 +19,6 @@ impl fmt::Display for Operator {
         let op_str = match self {
             Operator::Or => "|",
             Operator::And => "&",
-            Operator::Not => "!",
             Operator::Xor => "^",
             Operator::Iff => "=",
             Operator::Implies => ">",
@@ -25,45 +27,78 @@ impl fmt::Display for Operator {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AstNode {
-    Variable(char),
+    Constant(bool),
+    Variable(Symbol),
     BinaryOperator(Operator, Box<AstNode>, Box<AstNode>),
-    UnaryOperator(Operator, Box<AstNode>),
+    Not(Box<AstNode>),
 }
 
 impl fmt::Display for AstNode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            AstNode::Constant(value) => write!(f, "{}", *value as u8),
             AstNode::Variable(name) => write!(f, "{}", name),
             AstNode::BinaryOperator(op, left, right) => {
                 write!(f, "({} {} {})", left, op, right) // Format as "(left operator right)"
             }
-            AstNode::UnaryOperator(op, operand) => {
-                write!(f, "{}{}", op, operand) // Format as "operator operand"
+            AstNode::Not(operand) => {
+                write!(f, "!{}", operand) // Format as "!operand"
             }
         }
     }
 }
 
 impl TryFrom<&str> for AstNode {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(rpn: &str) -> Result<Self, Self::Error> {
-        let mut stack: LinkedList<AstNode> = LinkedList::new();
+        // Each operand keeps the byte offset where its subformula starts
+        let mut stack: LinkedList<(AstNode, usize)> = LinkedList::new();
 
         // Step 1: Iterate over the characters in reverse order to build the stack
-        for token in rpn.chars() {
+        let mut chars = rpn.char_indices();
+        while let Some((pos, token)) = chars.next() {
             match token {
-                'A'..='Z' => stack.push_back(AstNode::Variable(token)),
+                _ if token.is_whitespace() => {}
+                '0' | '1' => stack.push_back((AstNode::Constant(token == '1'), pos)),
+                'A'..='Z' => stack.push_back((AstNode::Variable(Symbol::from(token)), pos)),
+                // Names longer than a letter are written between braces: {x_12}, with a
+                // backslash before any brace or backslash of the name
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    let mut nested = false;
+                    while let Some((_, ch)) = chars.next() {
+                        match ch {
+                            '}' => {
+                                closed = true;
+                                break;
+                            }
+                            '{' => nested = true,
+                            '\\' => match chars.next() {
+                                Some((_, ch)) => name.push(ch),
+                                None => break,
+                            },
+                            _ => name.push(ch),
+                        }
+                    }
+
+                    if !closed || nested || name.trim().is_empty() {
+                        return Err(ParseError::InvalidName { pos });
+                    }
+                    stack.push_back((AstNode::Variable(Symbol::new(&name)), pos));
+                }
                 '|' | '&' | '^' | '=' | '>' => {
+                    let missing = ParseError::MissingOperand {
+                        pos,
+                        operator: token,
+                    };
+
                     // Ensure there are at least two operands for binary operators
-                    let right = stack
-                        .pop_back()
-                        .ok_or_else(|| "Missing operand for operator".to_string())?;
-                    let left = stack
-                        .pop_back()
-                        .ok_or_else(|| "Missing operand for operator".to_string())?;
+                    let (right, _) = stack.pop_back().ok_or(missing.clone())?;
+                    let (left, start) = stack.pop_back().ok_or(missing)?;
 
                     // Create the appropriate binary operator node
                     let operator = match token {
@@ -74,68 +109,70 @@ impl TryFrom<&str> for AstNode {
                         '>' => Operator::Implies,
                         _ => unreachable!(),
                     };
-                    stack.push_back(AstNode::BinaryOperator(
-                        operator,
-                        Box::new(left),
-                        Box::new(right),
+                    stack.push_back((
+                        AstNode::BinaryOperator(operator, Box::new(left), Box::new(right)),
+                        start,
                     ));
                 }
                 '!' => {
                     // Ensure there's at least one operand for the unary operator
-                    let element = stack
-                        .pop_back()
-                        .ok_or_else(|| "Missing operand for NOT".to_string())?;
-                    stack.push_back(AstNode::UnaryOperator(Operator::Not, Box::new(element)));
+                    let (element, start) = stack.pop_back().ok_or(ParseError::MissingOperand {
+                        pos,
+                        operator: token,
+                    })?;
+                    stack.push_back((AstNode::Not(Box::new(element)), start));
                 }
-                _ => return Err(format!("Unknown token: {}", token)),
+                _ => return Err(ParseError::UnknownToken { pos, token }),
             }
         }
 
         // Step 2: After the loop, the stack should have exactly one element (the final AST)
-        if stack.len() != 1 {
-            return Err(format!(
-                "Error: The stack should contain exactly one element, but it contains {}",
-                stack.len()
-            ));
+        match stack.len() {
+            0 => Err(ParseError::EmptyInput),
+            1 => Ok(stack.pop_back().unwrap().0), // Return the AST
+            count => Err(ParseError::LeftoverOperands {
+                // Point at the first operand that was never consumed
+                pos: stack.iter().nth(1).unwrap().1,
+                count,
+            }),
         }
-
-        Ok(stack.pop_back().unwrap()) // Return the AST
     }
 }
 
 impl AstNode {
-    pub fn get_variables(&self) -> HashSet<char> {
+    pub fn get_variables(&self) -> HashSet<Symbol> {
         let mut variables = HashSet::new();
-        match self {
-            AstNode::Variable(c) => {
-                variables.insert(*c);
-            }
-            AstNode::BinaryOperator(_, left, right) => {
-                variables.extend(left.get_variables());
-                variables.extend(right.get_variables());
-            }
-            AstNode::UnaryOperator(_, child) => {
-                variables.extend(child.get_variables());
+        // Explicit stack, so that deep formulas do not overflow the call stack
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            match node {
+                AstNode::Constant(_) => {}
+                AstNode::Variable(c) => {
+                    variables.insert(*c);
+                }
+                AstNode::BinaryOperator(_, left, right) => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+                AstNode::Not(child) => stack.push(child),
             }
         }
 
         variables
     }
 
-    pub fn evaluate(&self, vars: &HashMap<char, bool>) -> Result<bool, String> {
+    pub fn evaluate(&self, vars: &HashMap<Symbol, bool>) -> Result<bool, Error> {
         match self {
+            AstNode::Constant(value) => Ok(*value),
             AstNode::Variable(var) => match vars.get(var) {
                 Some(value) => Ok(*value),
-                None => Err(format!("Variable '{}' not found", var)),
+                None => Err(Error::UnboundVariable(*var)),
             },
 
-            AstNode::UnaryOperator(op, child) => match op {
-                Operator::Not => {
-                    let value = child.evaluate(vars)?;
-                    Ok(!value)
-                }
-                _ => panic!("Invalid unary operator"),
-            },
+            AstNode::Not(child) => {
+                let value = child.evaluate(vars)?;
+                Ok(!value)
+            }
 
             AstNode::BinaryOperator(op, left, right) => {
                 let left_val = left.evaluate(vars)?;
@@ -147,79 +184,105 @@ impl AstNode {
                     Operator::Xor => Ok(left_val ^ right_val),
                     Operator::Implies => Ok(!left_val | right_val),
                     Operator::Iff => Ok(left_val == right_val),
-                    _ => panic!("Invalid binary operator"),
                 }
             }
         }
     }
 
-    pub fn evaluate_set(
+    /// Evaluate the formula on sets: variables are bound to sets, `0` is the empty set, `1`
+    /// is `universe`, negation is the complement in `universe` and the binary operators are
+    /// the matching set operations. The sets are evaluated as bitsets by a [`SetEngine`].
+    pub fn evaluate_set_of<T>(
         &self,
-        sets: Vec<Vec<i32>>,
-        universal_set: HashSet<i32>,
-    ) -> Result<Vec<i32>, String> {
-        match self {
-            AstNode::Variable(var) => {
-                let idx = *var as usize - 'A' as usize;
-                sets.get(idx)
-                    .cloned()
-                    .ok_or_else(|| format!("Sets not found for variable {}", *var))
-            }
-            AstNode::UnaryOperator(op, child) => match op {
-                Operator::Not => {
-                    let child_set = child.evaluate_set(sets.clone(), universal_set.clone())?;
-                    let child_set: HashSet<i32> = child_set.into_iter().collect();
-                    let complement = universal_set
-                        .difference(&child_set)
-                        .cloned()
-                        .collect::<Vec<i32>>();
-                    Ok(complement)
-                }
-                _ => Err(format!("Invalid operator")),
-            },
-            AstNode::BinaryOperator(op, left, right) => {
-                let lset: Vec<i32> = left.evaluate_set(sets.clone(), universal_set.clone())?;
-                let lset: HashSet<i32> = lset.into_iter().collect();
-
-                let rset: Vec<i32> = right.evaluate_set(sets.clone(), universal_set.clone())?;
-                let rset: HashSet<i32> = rset.into_iter().collect();
+        sets: &HashMap<Symbol, HashSet<T>>,
+        universe: &HashSet<T>,
+    ) -> Result<HashSet<T>, Error>
+    where
+        T: Eq + Hash + Clone,
+    {
+        let bound: Vec<(Symbol, &HashSet<T>)> = self
+            .get_variables()
+            .into_iter()
+            .filter_map(|var| Some((var, sets.get(&var)?)))
+            .collect();
+        // Bound sets may have elements outside the universe, which only complements drop
+        let extra = bound.iter().flat_map(|(_, set)| set.iter()).cloned();
+        let engine = SetEngine::with_extra(universe.iter().cloned(), extra);
+
+        let bitsets: HashMap<Symbol, Bitset> = bound
+            .into_iter()
+            .map(|(var, set)| (var, engine.bitset(set).expect("Every element is indexed")))
+            .collect();
+        let result = engine.evaluate(self, &bitsets)?;
+        Ok(engine.elements(&result).cloned().collect())
+    }
 
-                match op {
-                    Operator::And => Ok(lset.intersection(&rset).cloned().collect::<Vec<i32>>()),
-                    Operator::Or => Ok(lset.union(&rset).cloned().collect::<Vec<i32>>()),
-                    Operator::Xor => Ok(lset
-                        .symmetric_difference(&rset)
-                        .cloned()
-                        .collect::<Vec<i32>>()),
-                    Operator::Implies => {
-                        let not_a = universal_set
-                            .difference(&lset)
-                            .cloned()
-                            .collect::<HashSet<_>>();
-                        Ok(not_a.union(&rset).cloned().collect::<Vec<i32>>())
-                    }
-                    Operator::Iff => {
-                        let sym_diff = lset
-                            .symmetric_difference(&rset)
-                            .cloned()
-                            .collect::<HashSet<_>>();
-                        Ok(universal_set
-                            .difference(&sym_diff)
-                            .cloned()
-                            .collect::<Vec<i32>>())
-                    }
-                    _ => Err(format!("Invalid operator")),
-                }
+    /// Same as [`AstNode::evaluate_set_of`], after checking that every variable of the
+    /// formula is bound to a subset of `universe`.
+    pub fn evaluate_set_in<T>(
+        &self,
+        sets: &HashMap<Symbol, HashSet<T>>,
+        universe: &HashSet<T>,
+    ) -> Result<HashSet<T>, Error>
+    where
+        T: Eq + Hash + Clone,
+    {
+        let mut variables: Vec<Symbol> = self.get_variables().into_iter().collect();
+        variables.sort();
+        for var in variables {
+            let set = sets.get(&var).ok_or(Error::UnboundVariable(var))?;
+            if !set.is_subset(universe) {
+                return Err(Error::OutsideUniverse(var));
             }
         }
+
+        self.evaluate_set_of(sets, universe)
+    }
+
+    /// Same as [`AstNode::evaluate_set_of`], with the result in order.
+    pub fn evaluate_set_of_sorted<T>(
+        &self,
+        sets: &HashMap<Symbol, HashSet<T>>,
+        universe: &HashSet<T>,
+    ) -> Result<BTreeSet<T>, Error>
+    where
+        T: Ord + Hash + Clone,
+    {
+        Ok(self.evaluate_set_of(sets, universe)?.into_iter().collect())
     }
 
-    pub fn truth_table(&self) -> Vec<(HashMap<char, bool>, bool)> {
-        let variables: HashSet<char> = self.get_variables();
+    /// Same as [`AstNode::evaluate_set_in`], with the result in order.
+    pub fn evaluate_set_in_sorted<T>(
+        &self,
+        sets: &HashMap<Symbol, HashSet<T>>,
+        universe: &HashSet<T>,
+    ) -> Result<BTreeSet<T>, Error>
+    where
+        T: Ord + Hash + Clone,
+    {
+        Ok(self.evaluate_set_in(sets, universe)?.into_iter().collect())
+    }
+
+    pub fn evaluate_set(
+        &self,
+        sets: HashMap<Symbol, Vec<i32>>,
+        universal_set: HashSet<i32>,
+    ) -> Result<Vec<i32>, Error> {
+        let sets = sets
+            .into_iter()
+            .map(|(var, set)| (var, set.into_iter().collect()))
+            .collect();
+        let result = self.evaluate_set_of_sorted(&sets, &universal_set)?;
+        Ok(result.into_iter().collect())
+    }
+
+    pub fn truth_table(&self) -> Vec<(HashMap<Symbol, bool>, bool)> {
+        let variables: HashSet<Symbol> = self.get_variables();
         let num_vars = variables.len();
 
         let mut truth_table = Vec::new();
-        let var_list: Vec<char> = variables.into_iter().collect();
+        let mut var_list: Vec<Symbol> = variables.into_iter().collect();
+        var_list.sort();
 
         // There are 2^n possible truth assignments for n variables
         for i in 0..(1 << num_vars) {
@@ -230,7 +293,9 @@ impl AstNode {
             }
 
             // Step 3: Evaluate the AST with the current variable assignments
-            let result = self.evaluate(&values).unwrap();
+            let result = self
+                .evaluate(&values)
+                .expect("Every variable is assigned a value");
 
             // Store the combination of variable assignments and the result
             truth_table.push((values, result));
@@ -239,85 +304,92 @@ impl AstNode {
         truth_table
     }
 
+    // Build `left ∧ right`, folding constant operands
+    pub(crate) fn conjunction(left: AstNode, right: AstNode) -> AstNode {
+        match (left, right) {
+            (AstNode::Constant(false), _) | (_, AstNode::Constant(false)) => {
+                AstNode::Constant(false)
+            }
+            (AstNode::Constant(true), other) | (other, AstNode::Constant(true)) => other,
+            (left, right) => {
+                AstNode::BinaryOperator(Operator::And, Box::new(left), Box::new(right))
+            }
+        }
+    }
+
+    // Build `left ∨ right`, folding constant operands
+    pub(crate) fn disjunction(left: AstNode, right: AstNode) -> AstNode {
+        match (left, right) {
+            (AstNode::Constant(true), _) | (_, AstNode::Constant(true)) => AstNode::Constant(true),
+            (AstNode::Constant(false), other) | (other, AstNode::Constant(false)) => other,
+            (left, right) => AstNode::BinaryOperator(Operator::Or, Box::new(left), Box::new(right)),
+        }
+    }
+
+    // Join `nodes` with `op` into a right-leaning tree, `empty` when there is nothing to join
+    pub(crate) fn right_fold(nodes: Vec<AstNode>, op: Operator, empty: bool) -> AstNode {
+        let mut iter = nodes.into_iter().rev();
+        match iter.next() {
+            Some(last) => iter.fold(last, |acc, node| {
+                AstNode::BinaryOperator(op.clone(), Box::new(node), Box::new(acc))
+            }),
+            None => AstNode::Constant(empty),
+        }
+    }
+
+    // NNF of `¬node`
+    fn negated_nnf(node: &AstNode) -> AstNode {
+        AstNode::Not(Box::new(node.clone())).to_nnf()
+    }
+
     pub fn to_nnf(&self) -> AstNode {
         match self {
-            // Variables remain unchanged
-            AstNode::Variable(_) => self.clone(),
+            // Variables and constants remain unchanged
+            AstNode::Variable(_) | AstNode::Constant(_) => self.clone(),
 
             // Handle unary operators (NOT)
-            AstNode::UnaryOperator(Operator::Not, child) => {
+            AstNode::Not(child) => {
                 match &**child {
+                    // Constant folding: ¬0 == 1, ¬1 == 0
+                    AstNode::Constant(value) => AstNode::Constant(!value),
+
                     // Double negation elimination: ¬¬A == A
-                    AstNode::UnaryOperator(Operator::Not, grandchild) => grandchild.to_nnf(),
+                    AstNode::Not(grandchild) => grandchild.to_nnf(),
 
                     // De Morgan's laws: ¬(A ∧ B) == (¬A ∨ ¬B)
-                    AstNode::BinaryOperator(Operator::And, left, right) => {
-                        let b = AstNode::UnaryOperator(Operator::Not, right.clone()).to_nnf();
-                        let a = AstNode::UnaryOperator(Operator::Not, left.clone()).to_nnf();
-                        AstNode::BinaryOperator(Operator::Or, Box::new(a), Box::new(b))
-                    }
+                    AstNode::BinaryOperator(Operator::And, left, right) => AstNode::disjunction(
+                        AstNode::negated_nnf(left),
+                        AstNode::negated_nnf(right),
+                    ),
 
                     // De Morgan's laws: ¬(A ∨ B) == (¬A ∧ ¬B)
-                    AstNode::BinaryOperator(Operator::Or, left, right) => {
-                        let b = AstNode::UnaryOperator(Operator::Not, right.clone()).to_nnf();
-                        let a = AstNode::UnaryOperator(Operator::Not, left.clone()).to_nnf();
-                        AstNode::BinaryOperator(Operator::And, Box::new(a), Box::new(b))
-                    }
+                    AstNode::BinaryOperator(Operator::Or, left, right) => AstNode::conjunction(
+                        AstNode::negated_nnf(left),
+                        AstNode::negated_nnf(right),
+                    ),
 
                     // Handle implication: ¬(A → B) == A ∧ ¬B
                     AstNode::BinaryOperator(Operator::Implies, left, right) => {
-                        let b = AstNode::UnaryOperator(Operator::Not, right.clone()).to_nnf();
-                        let a = left.to_nnf();
-                        AstNode::BinaryOperator(Operator::And, Box::new(a), Box::new(b))
+                        AstNode::conjunction(left.to_nnf(), AstNode::negated_nnf(right))
                     }
 
                     // Handle equivalence: ¬(A ↔ B) == (A ∧ ¬B) ∨ (¬A ∧ B)
-                    AstNode::BinaryOperator(Operator::Iff, left, right) => {
-                        let b = right.to_nnf();
-                        let bi = AstNode::UnaryOperator(Operator::Not, right.clone()).to_nnf();
-                        let a = left.to_nnf();
-                        let ai = AstNode::UnaryOperator(Operator::Not, left.clone()).to_nnf();
-                        AstNode::BinaryOperator(
-                            Operator::Or,
-                            Box::new(AstNode::BinaryOperator(
-                                Operator::And,
-                                Box::new(a),
-                                Box::new(bi),
-                            )),
-                            Box::new(AstNode::BinaryOperator(
-                                Operator::And,
-                                Box::new(ai),
-                                Box::new(b),
-                            )),
-                        )
-                    }
+                    AstNode::BinaryOperator(Operator::Iff, left, right) => AstNode::disjunction(
+                        AstNode::conjunction(left.to_nnf(), AstNode::negated_nnf(right)),
+                        AstNode::conjunction(AstNode::negated_nnf(left), right.to_nnf()),
+                    ),
 
                     // Handle XOR: ¬(A ⊕ B) == (A ↔ B) == (A ∧ B) ∨ (¬A ∧ ¬B)
-                    AstNode::BinaryOperator(Operator::Xor, left, right) => {
-                        let b = right.to_nnf();
-                        let bi = AstNode::UnaryOperator(Operator::Not, right.clone()).to_nnf();
-                        let a = left.to_nnf();
-                        let ai = AstNode::UnaryOperator(Operator::Not, left.clone()).to_nnf();
-
-                        AstNode::BinaryOperator(
-                            Operator::Or,
-                            Box::new(AstNode::BinaryOperator(
-                                Operator::And,
-                                Box::new(a),
-                                Box::new(b),
-                            )),
-                            Box::new(AstNode::BinaryOperator(
-                                Operator::And,
-                                Box::new(ai),
-                                Box::new(bi),
-                            )),
-                        )
-                    }
+                    AstNode::BinaryOperator(Operator::Xor, left, right) => AstNode::disjunction(
+                        AstNode::conjunction(left.to_nnf(), right.to_nnf()),
+                        AstNode::conjunction(
+                            AstNode::negated_nnf(left),
+                            AstNode::negated_nnf(right),
+                        ),
+                    ),
 
                     // For variables, keep the NOT
                     AstNode::Variable(_) => self.clone(),
-
-                    _ => panic!("Error"),
                 }
             }
 
@@ -325,64 +397,30 @@ impl AstNode {
             AstNode::BinaryOperator(op, left, right) => {
                 match op {
                     // AND and OR just need their children converted
-                    Operator::And | Operator::Or => {
-                        let b = right.to_nnf();
-                        let a = left.to_nnf();
-                        AstNode::BinaryOperator(op.clone(), Box::new(a), Box::new(b))
-                    }
+                    Operator::And => AstNode::conjunction(left.to_nnf(), right.to_nnf()),
+                    Operator::Or => AstNode::disjunction(left.to_nnf(), right.to_nnf()),
 
                     // A → B == ¬A ∨ B
                     Operator::Implies => {
-                        let b = right.to_nnf();
-                        let a = AstNode::UnaryOperator(Operator::Not, left.clone()).to_nnf();
-                        AstNode::BinaryOperator(Operator::Or, Box::new(a), Box::new(b))
+                        AstNode::disjunction(AstNode::negated_nnf(left), right.to_nnf())
                     }
 
                     // A ↔ B == (A ∧ B) ∨ (¬A ∧ ¬B)
-                    Operator::Iff => {
-                        let b = right.to_nnf();
-                        let bi = AstNode::UnaryOperator(Operator::Not, right.clone()).to_nnf();
-                        let a = left.to_nnf();
-                        let ai = AstNode::UnaryOperator(Operator::Not, left.clone()).to_nnf();
-                        AstNode::BinaryOperator(
-                            Operator::Or,
-                            Box::new(AstNode::BinaryOperator(
-                                Operator::And,
-                                Box::new(a),
-                                Box::new(b),
-                            )),
-                            Box::new(AstNode::BinaryOperator(
-                                Operator::And,
-                                Box::new(ai),
-                                Box::new(bi),
-                            )),
-                        )
-                    }
+                    Operator::Iff => AstNode::disjunction(
+                        AstNode::conjunction(left.to_nnf(), right.to_nnf()),
+                        AstNode::conjunction(
+                            AstNode::negated_nnf(left),
+                            AstNode::negated_nnf(right),
+                        ),
+                    ),
 
                     // A ⊕ B == (A ∧ ¬B) ∨ (¬A ∧ B)
-                    Operator::Xor => {
-                        let b = right.to_nnf();
-                        let bi = AstNode::UnaryOperator(Operator::Not, right.clone()).to_nnf();
-                        let a = left.to_nnf();
-                        let ai = AstNode::UnaryOperator(Operator::Not, left.clone()).to_nnf();
-                        AstNode::BinaryOperator(
-                            Operator::Or,
-                            Box::new(AstNode::BinaryOperator(
-                                Operator::And,
-                                Box::new(a),
-                                Box::new(bi),
-                            )),
-                            Box::new(AstNode::BinaryOperator(
-                                Operator::And,
-                                Box::new(ai),
-                                Box::new(b),
-                            )),
-                        )
-                    }
-                    _ => panic!("Error"),
+                    Operator::Xor => AstNode::disjunction(
+                        AstNode::conjunction(left.to_nnf(), AstNode::negated_nnf(right)),
+                        AstNode::conjunction(AstNode::negated_nnf(left), right.to_nnf()),
+                    ),
                 }
             }
-            _ => panic!("Error"),
         }
     }
 
@@ -391,7 +429,7 @@ impl AstNode {
         fn distribute(node: &AstNode) -> AstNode {
             match node {
                 // Base cases
-                AstNode::Variable(_) | AstNode::UnaryOperator(_, _) => node.clone(),
+                AstNode::Constant(_) | AstNode::Variable(_) | AstNode::Not(_) => node.clone(),
 
                 AstNode::BinaryOperator(op, left, right) => match op {
                     // AND: recursively convert both sides
@@ -449,7 +487,7 @@ impl AstNode {
                             ),
                         }
                     }
-                    _ => panic!("Unexpected operator in CNF conversion"),
+                    _ => unreachable!("NNF only contains AND, OR and negated variables"),
                 },
             }
         }
@@ -459,8 +497,81 @@ impl AstNode {
             .to_right_associative(&Operator::And)
     }
 
+    pub fn to_dnf(&self) -> AstNode {
+        // First convert to NNF then distribute AND over OR, the dual of the CNF conversion
+        fn distribute(node: &AstNode) -> AstNode {
+            match node {
+                // Base cases
+                AstNode::Constant(_) | AstNode::Variable(_) | AstNode::Not(_) => node.clone(),
+
+                AstNode::BinaryOperator(op, left, right) => match op {
+                    // OR: recursively convert both sides
+                    Operator::Or => AstNode::BinaryOperator(
+                        Operator::Or,
+                        Box::new(distribute(left)),
+                        Box::new(distribute(right)),
+                    ),
+
+                    // AND: need to handle distribution
+                    Operator::And => {
+                        let left_dnf = distribute(left);
+                        let right_dnf = distribute(right);
+
+                        match (&left_dnf, &right_dnf) {
+                            // Case: (A ∨ B) ∧ C -> (A ∧ C) ∨ (B ∧ C)
+                            (AstNode::BinaryOperator(Operator::Or, a, b), c) => {
+                                distribute(&AstNode::BinaryOperator(
+                                    Operator::Or,
+                                    Box::new(AstNode::BinaryOperator(
+                                        Operator::And,
+                                        a.clone(),
+                                        Box::new(c.clone()),
+                                    )),
+                                    Box::new(AstNode::BinaryOperator(
+                                        Operator::And,
+                                        b.clone(),
+                                        Box::new(c.clone()),
+                                    )),
+                                ))
+                            }
+
+                            // Case: A ∧ (B ∨ C) -> (A ∧ B) ∨ (A ∧ C)
+                            (a, AstNode::BinaryOperator(Operator::Or, b, c)) => {
+                                distribute(&AstNode::BinaryOperator(
+                                    Operator::Or,
+                                    Box::new(AstNode::BinaryOperator(
+                                        Operator::And,
+                                        Box::new(a.clone()),
+                                        b.clone(),
+                                    )),
+                                    Box::new(AstNode::BinaryOperator(
+                                        Operator::And,
+                                        Box::new(a.clone()),
+                                        c.clone(),
+                                    )),
+                                ))
+                            }
+
+                            // No OR to distribute over
+                            _ => AstNode::BinaryOperator(
+                                Operator::And,
+                                Box::new(left_dnf),
+                                Box::new(right_dnf),
+                            ),
+                        }
+                    }
+                    _ => unreachable!("NNF only contains AND, OR and negated variables"),
+                },
+            }
+        }
+
+        distribute(&self.to_nnf())
+            .to_right_associative(&Operator::And)
+            .to_right_associative(&Operator::Or)
+    }
+
     // Generic method to collect operands for associative operators
-    fn collect_operands(&self, target_op: &Operator) -> Vec<AstNode> {
+    pub(crate) fn collect_operands(&self, target_op: &Operator) -> Vec<AstNode> {
         match self {
             AstNode::BinaryOperator(op, left, right) if op == target_op => {
                 let mut operands = Vec::new();
@@ -474,11 +585,9 @@ impl AstNode {
 
     fn to_right_associative(&self, target_op: &Operator) -> AstNode {
         match self {
-            AstNode::Variable(_) => self.clone(),
+            AstNode::Constant(_) | AstNode::Variable(_) => self.clone(),
 
-            AstNode::UnaryOperator(op, child) => {
-                AstNode::UnaryOperator(op.clone(), Box::new(child.to_right_associative(target_op)))
-            }
+            AstNode::Not(child) => AstNode::Not(Box::new(child.to_right_associative(target_op))),
 
             AstNode::BinaryOperator(op, _, _) if op == target_op => {
                 // Collect all operands for this operator and build right-leaning tree
@@ -506,19 +615,22 @@ impl AstNode {
 
     pub fn to_rpn(&self) -> String {
         match self {
-            AstNode::Variable(var) => String::from(*var),
-
-            AstNode::UnaryOperator(op, child) => {
-                format!(
-                    "{}{}",
-                    child.to_rpn(),
-                    match op {
-                        Operator::Not => "!",
-                        _ => panic!("Unexpected unary operator"),
+            AstNode::Constant(value) => String::from(if *value { '1' } else { '0' }),
+            AstNode::Variable(var) if var.is_letter() => var.to_string(),
+            // Braces and backslashes in the name are escaped with a backslash
+            AstNode::Variable(var) => {
+                let mut name = String::from('{');
+                for ch in var.as_str().chars() {
+                    if matches!(ch, '{' | '}' | '\\') {
+                        name.push('\\');
                     }
-                )
+                    name.push(ch);
+                }
+                name + "}"
             }
 
+            AstNode::Not(child) => format!("{}!", child.to_rpn()),
+
             AstNode::BinaryOperator(op, left, right) => {
                 format!(
                     "{}{}{}",
@@ -530,10 +642,40 @@ impl AstNode {
                         Operator::Xor => "^",
                         Operator::Iff => "=",
                         Operator::Implies => ">",
-                        _ => panic!("Unexpected binary operator"),
                     }
                 )
             }
         }
     }
 }
+
+// The default drop recurses once per level, which overflows the stack on deep formulas
+// such as long conjunction chains, so operands are taken out and dropped from a stack
+impl Drop for AstNode {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        self.take_operands(&mut stack);
+        while let Some(mut node) = stack.pop() {
+            node.take_operands(&mut stack);
+        }
+    }
+}
+
+impl AstNode {
+    // Move the operands that have operands of their own onto `stack`, leaving constants
+    fn take_operands(&mut self, stack: &mut Vec<AstNode>) {
+        let mut take = |operand: &mut Box<AstNode>| {
+            if matches!(**operand, AstNode::Not(_) | AstNode::BinaryOperator(..)) {
+                stack.push(std::mem::replace(&mut **operand, AstNode::Constant(false)));
+            }
+        };
+        match self {
+            AstNode::Not(child) => take(child),
+            AstNode::BinaryOperator(_, left, right) => {
+                take(left);
+                take(right);
+            }
+            AstNode::Constant(_) | AstNode::Variable(_) => {}
+        }
+    }
+}
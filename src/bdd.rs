@@ -0,0 +1,388 @@
+use crate::ast::{AstNode, Operator};
+use crate::symbol::Symbol;
+use std::collections::{HashMap, HashSet};
+
+/// Handle to a node of a [`BddManager`]. Diagrams built by the same manager are canonical:
+/// two handles are equal exactly when they represent the same function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Bdd(u32);
+
+impl Bdd {
+    pub const FALSE: Bdd = Bdd(0);
+    pub const TRUE: Bdd = Bdd(1);
+
+    pub fn is_constant(&self) -> bool {
+        self.0 < 2
+    }
+}
+
+// Level of the terminals, below every variable
+const TERMINAL: u32 = u32::MAX;
+
+#[derive(Clone, Copy)]
+struct Node {
+    level: u32,
+    low: Bdd,
+    high: Bdd,
+}
+
+/// Owner of reduced ordered binary decision diagrams over a shared variable order.
+///
+/// Variables are ordered by first use unless an order is given with [`BddManager::with_order`].
+/// Nodes are never freed, so handles stay valid for the lifetime of the manager.
+pub struct BddManager {
+    nodes: Vec<Node>,
+    // Variable of every level and level of every variable
+    order: Vec<Symbol>,
+    levels: HashMap<Symbol, u32>,
+    // Unique table guaranteeing that no two nodes have the same level and children
+    unique: HashMap<(u32, Bdd, Bdd), Bdd>,
+    // Computed table of `ite` results
+    computed: HashMap<(Bdd, Bdd, Bdd), Bdd>,
+}
+
+impl Default for BddManager {
+    fn default() -> Self {
+        BddManager::new()
+    }
+}
+
+impl BddManager {
+    pub fn new() -> BddManager {
+        let terminal = |value| Node {
+            level: TERMINAL,
+            low: value,
+            high: value,
+        };
+
+        BddManager {
+            nodes: vec![terminal(Bdd::FALSE), terminal(Bdd::TRUE)],
+            order: Vec::new(),
+            levels: HashMap::new(),
+            unique: HashMap::new(),
+            computed: HashMap::new(),
+        }
+    }
+
+    /// Manager whose first variables are `order`, top to bottom.
+    pub fn with_order(order: &[Symbol]) -> BddManager {
+        let mut manager = BddManager::new();
+        for &name in order {
+            manager.level(name);
+        }
+        manager
+    }
+
+    /// Variables known to the manager, from the top of the order.
+    pub fn variables(&self) -> &[Symbol] {
+        &self.order
+    }
+
+    /// Number of nodes ever created, terminals included.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn level(&mut self, name: Symbol) -> u32 {
+        match self.levels.get(&name) {
+            Some(&level) => level,
+            None => {
+                let level = self.order.len() as u32;
+                self.order.push(name);
+                self.levels.insert(name, level);
+                level
+            }
+        }
+    }
+
+    fn node(&self, f: Bdd) -> Node {
+        self.nodes[f.0 as usize]
+    }
+
+    // The node `level ? high : low`, reduced and shared through the unique table
+    fn make(&mut self, level: u32, low: Bdd, high: Bdd) -> Bdd {
+        if low == high {
+            return low;
+        }
+        if let Some(&f) = self.unique.get(&(level, low, high)) {
+            return f;
+        }
+
+        let f = Bdd(self.nodes.len() as u32);
+        self.nodes.push(Node { level, low, high });
+        self.unique.insert((level, low, high), f);
+        f
+    }
+
+    // Cofactors of `f` with respect to the variable at `level`
+    fn cofactors(&self, f: Bdd, level: u32) -> (Bdd, Bdd) {
+        let node = self.node(f);
+        if node.level == level {
+            (node.low, node.high)
+        } else {
+            (f, f)
+        }
+    }
+
+    pub fn constant(&self, value: bool) -> Bdd {
+        if value {
+            Bdd::TRUE
+        } else {
+            Bdd::FALSE
+        }
+    }
+
+    pub fn var(&mut self, name: Symbol) -> Bdd {
+        let level = self.level(name);
+        self.make(level, Bdd::FALSE, Bdd::TRUE)
+    }
+
+    /// If-then-else: the function `f ? g : h`, the single operation every other
+    /// connective is built from.
+    pub fn ite(&mut self, f: Bdd, g: Bdd, h: Bdd) -> Bdd {
+        // Terminal cases
+        if f == Bdd::TRUE || g == h {
+            return g;
+        }
+        if f == Bdd::FALSE {
+            return h;
+        }
+        if g == Bdd::TRUE && h == Bdd::FALSE {
+            return f;
+        }
+        if let Some(&result) = self.computed.get(&(f, g, h)) {
+            return result;
+        }
+
+        let level = self
+            .node(f)
+            .level
+            .min(self.node(g).level)
+            .min(self.node(h).level);
+        let (f0, f1) = self.cofactors(f, level);
+        let (g0, g1) = self.cofactors(g, level);
+        let (h0, h1) = self.cofactors(h, level);
+        let low = self.ite(f0, g0, h0);
+        let high = self.ite(f1, g1, h1);
+        let result = self.make(level, low, high);
+
+        self.computed.insert((f, g, h), result);
+        result
+    }
+
+    pub fn not(&mut self, f: Bdd) -> Bdd {
+        self.ite(f, Bdd::FALSE, Bdd::TRUE)
+    }
+
+    pub fn apply(&mut self, op: &Operator, f: Bdd, g: Bdd) -> Bdd {
+        match op {
+            Operator::And => self.ite(f, g, Bdd::FALSE),
+            Operator::Or => self.ite(f, Bdd::TRUE, g),
+            Operator::Xor => {
+                let not_g = self.not(g);
+                self.ite(f, not_g, g)
+            }
+            Operator::Iff => {
+                let not_g = self.not(g);
+                self.ite(f, g, not_g)
+            }
+            Operator::Implies => self.ite(f, g, Bdd::TRUE),
+        }
+    }
+
+    /// The cofactor `f[name := value]`.
+    pub fn restrict(&mut self, f: Bdd, name: Symbol, value: bool) -> Bdd {
+        let Some(&level) = self.levels.get(&name) else {
+            return f;
+        };
+
+        fn go(
+            manager: &mut BddManager,
+            f: Bdd,
+            level: u32,
+            value: bool,
+            cache: &mut HashMap<Bdd, Bdd>,
+        ) -> Bdd {
+            let node = manager.node(f);
+            // Nodes below the variable do not depend on it
+            if node.level > level {
+                return f;
+            }
+            if node.level == level {
+                return if value { node.high } else { node.low };
+            }
+            if let Some(&result) = cache.get(&f) {
+                return result;
+            }
+
+            let low = go(manager, node.low, level, value, cache);
+            let high = go(manager, node.high, level, value, cache);
+            let result = manager.make(node.level, low, high);
+            cache.insert(f, result);
+            result
+        }
+
+        go(self, f, level, value, &mut HashMap::new())
+    }
+
+    /// Existential quantification `∃ names. f`.
+    pub fn exists(&mut self, f: Bdd, names: &[Symbol]) -> Bdd {
+        names.iter().fold(f, |f, &name| {
+            let low = self.restrict(f, name, false);
+            let high = self.restrict(f, name, true);
+            self.apply(&Operator::Or, low, high)
+        })
+    }
+
+    /// Universal quantification `∀ names. f`.
+    pub fn forall(&mut self, f: Bdd, names: &[Symbol]) -> Bdd {
+        names.iter().fold(f, |f, &name| {
+            let low = self.restrict(f, name, false);
+            let high = self.restrict(f, name, true);
+            self.apply(&Operator::And, low, high)
+        })
+    }
+
+    /// Number of assignments to all the variables of the manager that satisfy `f`, `None`
+    /// when it does not fit in a `u128`, which only happens with 128 variables or more.
+    pub fn sat_count(&self, f: Bdd) -> Option<u128> {
+        let num_vars = self.order.len() as u32;
+
+        // `count * 2^free`, for the models of `free` unconstrained variables
+        fn scale(count: u128, free: u32) -> Option<u128> {
+            if count == 0 {
+                return Some(0);
+            }
+            count.checked_mul(1u128.checked_shl(free)?)
+        }
+
+        // Models of the node over the variables at its level and below
+        fn go(
+            manager: &BddManager,
+            f: Bdd,
+            num_vars: u32,
+            cache: &mut HashMap<Bdd, u128>,
+        ) -> Option<u128> {
+            if f.is_constant() {
+                return Some((f == Bdd::TRUE) as u128);
+            }
+            if let Some(&count) = cache.get(&f) {
+                return Some(count);
+            }
+
+            let node = manager.node(f);
+            let child = |manager: &BddManager, g: Bdd, cache: &mut HashMap<Bdd, u128>| {
+                let level = manager.node(g).level.min(num_vars);
+                // Variables skipped between the node and its child are free
+                scale(go(manager, g, num_vars, cache)?, level - node.level - 1)
+            };
+            let count =
+                child(manager, node.low, cache)?.checked_add(child(manager, node.high, cache)?)?;
+            cache.insert(f, count);
+            Some(count)
+        }
+
+        let level = self.node(f).level.min(num_vars);
+        scale(go(self, f, num_vars, &mut HashMap::new())?, level)
+    }
+
+    /// Satisfying cubes of `f`, one per path to the true terminal. Variables missing from a
+    /// cube may take either value, and distinct cubes have no model in common.
+    pub fn cubes(&self, f: Bdd) -> Vec<HashMap<Symbol, bool>> {
+        fn go(
+            manager: &BddManager,
+            f: Bdd,
+            path: &mut Vec<(Symbol, bool)>,
+            cubes: &mut Vec<HashMap<Symbol, bool>>,
+        ) {
+            if f == Bdd::FALSE {
+                return;
+            }
+            if f == Bdd::TRUE {
+                cubes.push(path.iter().copied().collect());
+                return;
+            }
+
+            let node = manager.node(f);
+            let name = manager.order[node.level as usize];
+            for (child, value) in [(node.low, false), (node.high, true)] {
+                path.push((name, value));
+                go(manager, child, path, cubes);
+                path.pop();
+            }
+        }
+
+        let mut cubes = Vec::new();
+        go(self, f, &mut Vec::new(), &mut cubes);
+        cubes
+    }
+
+    /// One satisfying cube of `f`, `None` when it is unsatisfiable.
+    pub fn any_sat(&self, f: Bdd) -> Option<HashMap<Symbol, bool>> {
+        if f == Bdd::FALSE {
+            return None;
+        }
+
+        let mut cube = HashMap::new();
+        let mut f = f;
+        while !f.is_constant() {
+            let node = self.node(f);
+            let name = self.order[node.level as usize];
+            // Every non-terminal node has a path to true, so either child works unless it is false
+            let value = node.low == Bdd::FALSE;
+            cube.insert(name, value);
+            f = if value { node.high } else { node.low };
+        }
+        Some(cube)
+    }
+
+    /// Number of nodes reachable from `f`, terminals included.
+    pub fn size(&self, f: Bdd) -> usize {
+        let mut seen = HashSet::from([f]);
+        let mut stack = vec![f];
+        while let Some(g) = stack.pop() {
+            if g.is_constant() {
+                continue;
+            }
+            let node = self.node(g);
+            for child in [node.low, node.high] {
+                if seen.insert(child) {
+                    stack.push(child);
+                }
+            }
+        }
+        seen.len()
+    }
+
+    /// The Shannon expansion of `f` as a formula, with constant branches folded.
+    pub fn to_ast(&self, f: Bdd) -> AstNode {
+        if f.is_constant() {
+            return AstNode::Constant(f == Bdd::TRUE);
+        }
+
+        let node = self.node(f);
+        let var = AstNode::Variable(self.order[node.level as usize]);
+        let high = AstNode::conjunction(var.clone(), self.to_ast(node.high));
+        let low = AstNode::conjunction(AstNode::Not(Box::new(var)), self.to_ast(node.low));
+        AstNode::disjunction(high, low)
+    }
+}
+
+impl AstNode {
+    /// Diagram of the formula in `manager`, adding its variables to the order on first use.
+    pub fn to_bdd(&self, manager: &mut BddManager) -> Bdd {
+        match self {
+            AstNode::Constant(value) => manager.constant(*value),
+            AstNode::Variable(name) => manager.var(*name),
+            AstNode::Not(child) => {
+                let child = child.to_bdd(manager);
+                manager.not(child)
+            }
+            AstNode::BinaryOperator(op, left, right) => {
+                let left = left.to_bdd(manager);
+                let right = right.to_bdd(manager);
+                manager.apply(op, left, right)
+            }
+        }
+    }
+}
@@ -1,7 +1,13 @@
 use crate::ast::AstNode;
-use std::collections::{HashSet, LinkedList};
+use crate::error::{Error, ParseError};
+use crate::kmap::KarnaughMap;
+use crate::solver::Solution;
+use crate::symbol::Symbol;
+use std::collections::{BTreeSet, HashMap, HashSet, LinkedList};
+use std::hash::Hash;
 
 // Add two numbers using only bitwise, shift and comparison operators
+#[allow(clippy::assign_op_pattern, clippy::needless_return)]
 pub fn adder(a: u32, b: u32) -> u32 {
     let mut carry;
     let mut res = a;
@@ -17,6 +23,7 @@ pub fn adder(a: u32, b: u32) -> u32 {
 }
 
 // Multiply two numbers using only bitwise, shift and comparison operators
+#[allow(clippy::assign_op_pattern, clippy::needless_return)]
 pub fn multiplier(a: u32, b: u32) -> u32 {
     let mut res = 0;
     let mut mul = b;
@@ -34,15 +41,21 @@ pub fn multiplier(a: u32, b: u32) -> u32 {
     return res;
 }
 
+#[allow(clippy::needless_return)]
 pub fn gray_code(a: u32) -> u32 {
     return a ^ (a >> 1);
 }
 
-pub fn eval_formula(formula: &str) -> Result<bool, String> {
+pub fn eval_formula(formula: &str) -> Result<bool, ParseError> {
     let mut stack: LinkedList<bool> = LinkedList::new();
 
     // Helper function to evaluate binary operations
-    fn eval_binary_op<F>(stack: &mut LinkedList<bool>, op: F) -> Result<(), String>
+    fn eval_binary_op<F>(
+        stack: &mut LinkedList<bool>,
+        pos: usize,
+        operator: char,
+        op: F,
+    ) -> Result<(), ParseError>
     where
         F: Fn(bool, bool) -> bool,
     {
@@ -50,12 +63,17 @@ pub fn eval_formula(formula: &str) -> Result<bool, String> {
             stack.push_back(op(a, b));
             Ok(())
         } else {
-            Err("Not enough values in stack for operator".to_string())
+            Err(ParseError::MissingOperand { pos, operator })
         }
     }
 
     // Helper function to evaluate unary operations
-    fn eval_unary_op<F>(stack: &mut LinkedList<bool>, op: F) -> Result<(), String>
+    fn eval_unary_op<F>(
+        stack: &mut LinkedList<bool>,
+        pos: usize,
+        operator: char,
+        op: F,
+    ) -> Result<(), ParseError>
     where
         F: Fn(bool) -> bool,
     {
@@ -63,102 +81,158 @@ pub fn eval_formula(formula: &str) -> Result<bool, String> {
             stack.push_back(op(a));
             Ok(())
         } else {
-            Err("Not enough values in stack for unary operator".to_string())
+            Err(ParseError::MissingOperand { pos, operator })
         }
     }
 
+    // Byte offset where each value on the stack starts, used to report leftovers
+    let mut starts: Vec<usize> = Vec::new();
+
     // Iterate over the formula characters and process each one
-    for ch in formula.chars() {
+    for (pos, ch) in formula.char_indices() {
         match ch {
             '0' => stack.push_back(false),
             '1' => stack.push_back(true),
-            '|' => eval_binary_op(&mut stack, |a, b| a | b)?,
-            '&' => eval_binary_op(&mut stack, |a, b| a & b)?,
-            '^' => eval_binary_op(&mut stack, |a, b| a ^ b)?,
-            '>' => eval_binary_op(&mut stack, |a, b| !a | b)?,
-            '=' => eval_binary_op(&mut stack, |a, b| a == b)?,
-            '!' => eval_unary_op(&mut stack, |a| !a)?,
-            _ => return Err(format!("Invalid character encountered: {}", ch)),
+            '|' => eval_binary_op(&mut stack, pos, ch, |a, b| a | b)?,
+            '&' => eval_binary_op(&mut stack, pos, ch, |a, b| a & b)?,
+            '^' => eval_binary_op(&mut stack, pos, ch, |a, b| a ^ b)?,
+            '>' => eval_binary_op(&mut stack, pos, ch, |a, b| !a | b)?,
+            '=' => eval_binary_op(&mut stack, pos, ch, |a, b| a == b)?,
+            '!' => eval_unary_op(&mut stack, pos, ch, |a| !a)?,
+            _ => return Err(ParseError::UnknownToken { pos, token: ch }),
+        }
+
+        // Operands push a new start, binary operators merge the two topmost ones
+        match ch {
+            '0' | '1' => starts.push(pos),
+            '!' => {}
+            _ => {
+                starts.pop();
+            }
         }
     }
 
     // Final stack check
-    if stack.len() == 1 {
-        return Ok(stack.pop_back().unwrap());
-    } else {
-        return Err("Invalid formula: stack has more than one value or is empty".to_string());
+    match stack.len() {
+        0 => Err(ParseError::EmptyInput),
+        1 => Ok(stack.pop_back().unwrap()),
+        count => Err(ParseError::LeftoverOperands {
+            pos: starts[1],
+            count,
+        }),
     }
 }
 
 pub fn print_truth_table(formula: &str) {
-    // Get the truth table from the existing method
-    let ast = AstNode::try_from(formula).expect("Can't create AST from formula");
-    let truth_table = ast.truth_table();
-
-    // Check if the truth table is empty
-    if truth_table.is_empty() {
-        println!("No truth table to print.");
-        return;
-    }
+    try_print_truth_table(formula).expect("Can't create AST from formula");
+}
 
-    // Get the variables from the truth table
-    let mut variables: Vec<char> = ast.get_variables().into_iter().collect();
-    variables.sort();
+pub fn try_print_truth_table(formula: &str) -> Result<(), Error> {
+    // Compute the whole table at once, over the sorted variables
+    let ast = AstNode::try_from(formula)?;
+    let truth_table = ast.try_to_truth_table()?;
+    let variables = truth_table.variables();
 
-    // Print the header
-    for v in &variables {
+    // Print the header, each column is as wide as its variable name
+    for v in variables {
         print!("| {} ", v);
     }
     println!("| = |");
 
     // Print the separator line
-    for _ in &variables {
-        print!("|---");
+    for v in variables {
+        print!("|{}", "-".repeat(v.as_str().chars().count() + 2));
     }
     println!("|---|");
 
     // Print each row of the truth table
-    for (tt_variables, tt_result) in truth_table {
-        for v in &variables {
-            print!("| {} ", if *tt_variables.get(v).unwrap() { 1 } else { 0 });
+    for row in 0..truth_table.rows() {
+        for (i, v) in variables.iter().enumerate() {
+            let value = row >> (variables.len() - 1 - i) & 1;
+            print!("| {:<width$} ", value, width = v.as_str().chars().count());
         }
-        println!("| {} |", if tt_result { 1 } else { 0 });
+        println!("| {} |", if truth_table.value(row) { 1 } else { 0 });
     }
+
+    Ok(())
+}
+
+pub fn print_karnaugh_map(formula: &str) {
+    try_print_karnaugh_map(formula).expect("Can't create AST from formula")
+}
+
+pub fn try_print_karnaugh_map(formula: &str) -> Result<(), Error> {
+    let ast = AstNode::try_from(formula)?;
+    print!("{}", KarnaughMap::new(&ast)?);
+
+    Ok(())
 }
 
 pub fn negation_normal_form(formula: &str) -> String {
-    let ast = AstNode::try_from(formula).expect("Can't create AST from formula");
+    try_negation_normal_form(formula).expect("Can't create AST from formula")
+}
+
+pub fn try_negation_normal_form(formula: &str) -> Result<String, Error> {
+    let ast = AstNode::try_from(formula)?;
     let nnf = ast.to_nnf();
 
-    return nnf.to_rpn();
+    Ok(nnf.to_rpn())
 }
 
 pub fn conjunctive_normal_form(formula: &str) -> String {
-    let ast = AstNode::try_from(formula).expect("Can't create AST from formula");
+    try_conjunctive_normal_form(formula).expect("Can't create AST from formula")
+}
+
+pub fn try_conjunctive_normal_form(formula: &str) -> Result<String, Error> {
+    let ast = AstNode::try_from(formula)?;
     let cnf = ast.to_cnf();
 
-    return cnf.to_rpn();
+    Ok(cnf.to_rpn())
+}
+
+pub fn disjunctive_normal_form(formula: &str) -> String {
+    try_disjunctive_normal_form(formula).expect("Can't create AST from formula")
+}
+
+pub fn try_disjunctive_normal_form(formula: &str) -> Result<String, Error> {
+    let ast = AstNode::try_from(formula)?;
+    let dnf = ast.to_dnf();
+
+    Ok(dnf.to_rpn())
+}
+
+pub fn algebraic_normal_form(formula: &str) -> String {
+    try_algebraic_normal_form(formula).expect("Can't create AST from formula")
+}
+
+pub fn try_algebraic_normal_form(formula: &str) -> Result<String, Error> {
+    let ast = AstNode::try_from(formula)?;
+    let anf = ast.to_anf()?;
+
+    Ok(anf.to_rpn())
 }
 
 pub fn sat(formula: &str) -> bool {
-    let ast = AstNode::try_from(formula).expect("Can't create AST from formula");
-    let truth_table = ast.truth_table();
+    try_sat(formula).expect("Can't create AST from formula")
+}
 
-    // Check if the truth table is empty
-    if truth_table.is_empty() {
-        return false;
-    }
+pub fn try_sat(formula: &str) -> Result<bool, Error> {
+    let ast = AstNode::try_from(formula)?;
 
-    // Check if a combination of values return true
-    for (_, result) in truth_table {
-        if result {
-            return true;
-        }
-    }
+    Ok(ast.is_satisfiable())
+}
+
+pub fn solve(formula: &str) -> Solution {
+    try_solve(formula).expect("Can't create AST from formula")
+}
 
-    return false;
+pub fn try_solve(formula: &str) -> Result<Solution, Error> {
+    let ast = AstNode::try_from(formula)?;
+
+    Ok(ast.solve())
 }
 
+#[allow(clippy::needless_return)]
 pub fn powerset(set: Vec<i32>) -> Vec<Vec<i32>> {
     let mut result = Vec::new();
 
@@ -179,21 +253,104 @@ pub fn powerset(set: Vec<i32>) -> Vec<Vec<i32>> {
 }
 
 pub fn evaluate_set(formula: &str, sets: Vec<Vec<i32>>) -> Vec<i32> {
-    let ast = AstNode::try_from(formula).expect("Can't create AST from formula");
+    try_evaluate_set(formula, sets).unwrap_or_else(|err| panic!("{}", err))
+}
+
+pub fn try_evaluate_set(formula: &str, sets: Vec<Vec<i32>>) -> Result<Vec<i32>, Error> {
+    Ok(try_evaluate_set_of_sorted(formula, sets)?
+        .into_iter()
+        .collect())
+}
+
+pub fn evaluate_set_of<T: Eq + Hash + Clone>(formula: &str, sets: Vec<Vec<T>>) -> HashSet<T> {
+    try_evaluate_set_of(formula, sets).unwrap_or_else(|err| panic!("{}", err))
+}
+
+// Sets of any hashable elements, the n-th set being bound to the n-th letter
+pub fn try_evaluate_set_of<T: Eq + Hash + Clone>(
+    formula: &str,
+    sets: Vec<Vec<T>>,
+) -> Result<HashSet<T>, Error> {
+    let ast = AstNode::try_from(formula)?;
     let variables = ast.get_variables();
 
     if variables.len() != sets.len() {
-        panic!("Variables length and sets length are not equal");
+        return Err(Error::SetCount {
+            variables: variables.len(),
+            sets: sets.len(),
+        });
     }
 
-    let universal_set: HashSet<i32> = sets.iter().flatten().cloned().collect();
+    let universal_set: HashSet<T> = sets.iter().flatten().cloned().collect();
 
-    let mut result = ast.evaluate_set(sets, universal_set).unwrap();
-    result.sort();
+    // The n-th set is bound to the n-th letter of the alphabet
+    let sets: HashMap<Symbol, HashSet<T>> = ('A'..='Z')
+        .map(Symbol::from)
+        .zip(sets)
+        .map(|(var, set)| (var, set.into_iter().collect()))
+        .collect();
 
-    return result;
+    ast.evaluate_set_of(&sets, &universal_set)
+}
+
+pub fn evaluate_set_of_sorted<T: Ord + Hash + Clone>(
+    formula: &str,
+    sets: Vec<Vec<T>>,
+) -> BTreeSet<T> {
+    try_evaluate_set_of_sorted(formula, sets).unwrap_or_else(|err| panic!("{}", err))
+}
+
+pub fn try_evaluate_set_of_sorted<T: Ord + Hash + Clone>(
+    formula: &str,
+    sets: Vec<Vec<T>>,
+) -> Result<BTreeSet<T>, Error> {
+    Ok(try_evaluate_set_of(formula, sets)?.into_iter().collect())
+}
+
+pub fn evaluate_set_in<T: Eq + Hash + Clone>(
+    formula: &str,
+    universe: Vec<T>,
+    sets: HashMap<Symbol, Vec<T>>,
+) -> HashSet<T> {
+    try_evaluate_set_in(formula, universe, sets).unwrap_or_else(|err| panic!("{}", err))
+}
+
+// Unlike `evaluate_set`, sets are bound by variable name and complements are taken in the
+// given universe
+pub fn try_evaluate_set_in<T: Eq + Hash + Clone>(
+    formula: &str,
+    universe: Vec<T>,
+    sets: HashMap<Symbol, Vec<T>>,
+) -> Result<HashSet<T>, Error> {
+    let ast = AstNode::try_from(formula)?;
+    let universe: HashSet<T> = universe.into_iter().collect();
+    let sets: HashMap<Symbol, HashSet<T>> = sets
+        .into_iter()
+        .map(|(var, set)| (var, set.into_iter().collect()))
+        .collect();
+
+    ast.evaluate_set_in(&sets, &universe)
+}
+
+pub fn evaluate_set_in_sorted<T: Ord + Hash + Clone>(
+    formula: &str,
+    universe: Vec<T>,
+    sets: HashMap<Symbol, Vec<T>>,
+) -> BTreeSet<T> {
+    try_evaluate_set_in_sorted(formula, universe, sets).unwrap_or_else(|err| panic!("{}", err))
+}
+
+pub fn try_evaluate_set_in_sorted<T: Ord + Hash + Clone>(
+    formula: &str,
+    universe: Vec<T>,
+    sets: HashMap<Symbol, Vec<T>>,
+) -> Result<BTreeSet<T>, Error> {
+    Ok(try_evaluate_set_in(formula, universe, sets)?
+        .into_iter()
+        .collect())
 }
 
+#[allow(clippy::needless_return)]
 pub fn map(x: u16, y: u16) -> f64 {
     let mut result: u32 = 0;
 
@@ -207,6 +364,7 @@ pub fn map(x: u16, y: u16) -> f64 {
     return result as f64 / u32::MAX as f64;
 }
 
+#[allow(clippy::needless_return)]
 pub fn reverse_map(z: f64) -> (u16, u16) {
     let mut x: u16 = 0;
     let mut y: u16 = 0;
@@ -0,0 +1,108 @@
+use std::fmt;
+use std::ops::Not;
+
+/// A literal: a propositional variable, numbered from 0, or its negation.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Lit(u32);
+
+impl Lit {
+    pub fn new(var: u32, negated: bool) -> Lit {
+        Lit(var << 1 | negated as u32)
+    }
+
+    pub fn positive(var: u32) -> Lit {
+        Lit::new(var, false)
+    }
+
+    pub fn negative(var: u32) -> Lit {
+        Lit::new(var, true)
+    }
+
+    pub fn var(&self) -> u32 {
+        self.0 >> 1
+    }
+
+    pub fn is_negated(&self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    /// Literal from its DIMACS number: variable `n - 1`, negated when `n` is negative.
+    pub fn from_dimacs(n: i64) -> Lit {
+        Lit::new(n.unsigned_abs() as u32 - 1, n < 0)
+    }
+
+    pub fn to_dimacs(&self) -> i64 {
+        let n = self.var() as i64 + 1;
+        if self.is_negated() {
+            -n
+        } else {
+            n
+        }
+    }
+
+    // Dense index of the literal, `2 * var` for the positive and `2 * var + 1` for the negative one
+    pub(crate) fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+
+    fn not(self) -> Lit {
+        Lit(self.0 ^ 1)
+    }
+}
+
+impl fmt::Debug for Lit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_negated() {
+            write!(f, "¬x{}", self.var())
+        } else {
+            write!(f, "x{}", self.var())
+        }
+    }
+}
+
+/// A formula in conjunctive normal form as a set of clauses over numbered variables.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Cnf {
+    num_vars: u32,
+    clauses: Vec<Vec<Lit>>,
+}
+
+impl Cnf {
+    pub fn new() -> Cnf {
+        Cnf::default()
+    }
+
+    /// Empty clause set over `num_vars` variables.
+    pub fn with_vars(num_vars: u32) -> Cnf {
+        Cnf {
+            num_vars,
+            clauses: Vec::new(),
+        }
+    }
+
+    /// Allocate a fresh variable.
+    pub fn new_var(&mut self) -> u32 {
+        self.num_vars += 1;
+        self.num_vars - 1
+    }
+
+    /// Add a clause, growing the variable count to cover every literal in it.
+    pub fn add_clause(&mut self, clause: Vec<Lit>) {
+        if let Some(max) = clause.iter().map(|lit| lit.var() + 1).max() {
+            self.num_vars = self.num_vars.max(max);
+        }
+        self.clauses.push(clause);
+    }
+
+    pub fn num_vars(&self) -> u32 {
+        self.num_vars
+    }
+
+    pub fn clauses(&self) -> &[Vec<Lit>] {
+        &self.clauses
+    }
+}
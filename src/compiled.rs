@@ -0,0 +1,276 @@
+use crate::ast::{AstNode, Operator};
+use crate::symbol::Symbol;
+use std::fmt;
+
+/// Instruction of a [`CompiledFormula`], writing one register.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Instruction {
+    Constant {
+        dst: u8,
+        value: bool,
+    },
+    // Value of the variable at this index of the formula's variables
+    Load {
+        dst: u8,
+        variable: u32,
+    },
+    Not {
+        dst: u8,
+        src: u8,
+    },
+    BinaryOperator {
+        op: Operator,
+        dst: u8,
+        left: u8,
+        right: u8,
+    },
+}
+
+/// A formula lowered to a linear sequence of register instructions over its sorted
+/// variables, for evaluating it many times without recursion, lookups or allocation.
+///
+/// Registers are allocated by Sethi-Ullman numbering: the operand needing more registers
+/// is computed first, so a formula needs at most one register more than the base 2
+/// logarithm of its number of leaves, and never more than [`CompiledFormula::REGISTERS`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompiledFormula {
+    variables: Vec<Symbol>,
+    instructions: Vec<Instruction>,
+    registers: usize,
+}
+
+impl CompiledFormula {
+    /// Number of registers available to a program.
+    pub const REGISTERS: usize = 64;
+
+    pub fn new(node: &AstNode) -> CompiledFormula {
+        let mut variables: Vec<Symbol> = node.get_variables().into_iter().collect();
+        variables.sort();
+        let mut compiled = CompiledFormula {
+            variables,
+            instructions: Vec::new(),
+            registers: 0,
+        };
+        compiled.compile(node);
+        compiled
+    }
+
+    pub fn variables(&self) -> &[Symbol] {
+        &self.variables
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Number of registers the program uses.
+    pub fn registers(&self) -> usize {
+        self.registers
+    }
+
+    // Emit the code computing `node` into register 0, each operand into the register of its
+    // parent or the next one. Both passes use explicit stacks, so that deep formulas do not
+    // overflow the call stack.
+    fn compile(&mut self, node: &AstNode) {
+        // Nodes with the indices of their operands, every node before its operands
+        let mut nodes: Vec<(&AstNode, [usize; 2])> = vec![(node, [0, 0])];
+        let mut i = 0;
+        while i < nodes.len() {
+            let operands = match nodes[i].0 {
+                AstNode::Not(child) => {
+                    nodes.push((child, [0, 0]));
+                    [nodes.len() - 1, 0]
+                }
+                AstNode::BinaryOperator(_, left, right) => {
+                    nodes.push((left, [0, 0]));
+                    nodes.push((right, [0, 0]));
+                    [nodes.len() - 2, nodes.len() - 1]
+                }
+                AstNode::Constant(_) | AstNode::Variable(_) => [0, 0],
+            };
+            nodes[i].1 = operands;
+            i += 1;
+        }
+
+        // Registers needed to compute each node without spilling, operands first
+        let mut need = vec![1; nodes.len()];
+        for i in (0..nodes.len()).rev() {
+            let [left, right] = nodes[i].1;
+            need[i] = match nodes[i].0 {
+                AstNode::Not(_) => need[left],
+                AstNode::BinaryOperator(..) if need[left] == need[right] => need[left] + 1,
+                AstNode::BinaryOperator(..) => need[left].max(need[right]),
+                AstNode::Constant(_) | AstNode::Variable(_) => 1,
+            };
+        }
+
+        enum Task {
+            // Emit the code of a node into a register
+            Compile(usize, u8),
+            // Emit the instruction of a node once its operands are in their registers
+            Apply {
+                index: usize,
+                dst: u8,
+                left: u8,
+                right: u8,
+            },
+        }
+
+        let mut tasks = vec![Task::Compile(0, 0)];
+        while let Some(task) = tasks.pop() {
+            let instruction = match task {
+                Task::Compile(index, dst) => {
+                    self.registers = self.registers.max(dst as usize + 1);
+                    let [left, right] = nodes[index].1;
+                    match nodes[index].0 {
+                        AstNode::Constant(value) => Instruction::Constant { dst, value: *value },
+                        AstNode::Variable(name) => {
+                            let variable = self.variables.binary_search(name).unwrap();
+                            Instruction::Load {
+                                dst,
+                                variable: variable as u32,
+                            }
+                        }
+                        AstNode::Not(_) => {
+                            tasks.push(Task::Apply {
+                                index,
+                                dst,
+                                left: dst,
+                                right: dst,
+                            });
+                            tasks.push(Task::Compile(left, dst));
+                            continue;
+                        }
+                        // The operand needing more registers is computed first
+                        AstNode::BinaryOperator(..) => {
+                            let (first, second) = if need[left] >= need[right] {
+                                (left, right)
+                            } else {
+                                (right, left)
+                            };
+                            let reg = |operand| if operand == first { dst } else { dst + 1 };
+                            tasks.push(Task::Apply {
+                                index,
+                                dst,
+                                left: reg(left),
+                                right: reg(right),
+                            });
+                            tasks.push(Task::Compile(second, dst + 1));
+                            tasks.push(Task::Compile(first, dst));
+                            continue;
+                        }
+                    }
+                }
+                Task::Apply {
+                    index,
+                    dst,
+                    left,
+                    right,
+                } => match nodes[index].0 {
+                    AstNode::BinaryOperator(op, _, _) => Instruction::BinaryOperator {
+                        op: op.clone(),
+                        dst,
+                        left,
+                        right,
+                    },
+                    _ => Instruction::Not { dst, src: left },
+                },
+            };
+            self.instructions.push(instruction);
+        }
+    }
+
+    // Run the program on 64 assignments at once, `load` giving the column of a variable
+    fn run(&self, load: impl Fn(usize) -> u64) -> u64 {
+        let mut registers = [0u64; Self::REGISTERS];
+        for instruction in &self.instructions {
+            match instruction {
+                Instruction::Constant { dst, value } => {
+                    registers[*dst as usize] = if *value { !0 } else { 0 };
+                }
+                Instruction::Load { dst, variable } => {
+                    registers[*dst as usize] = load(*variable as usize);
+                }
+                Instruction::Not { dst, src } => {
+                    registers[*dst as usize] = !registers[*src as usize];
+                }
+                Instruction::BinaryOperator {
+                    op,
+                    dst,
+                    left,
+                    right,
+                } => {
+                    let (a, b) = (registers[*left as usize], registers[*right as usize]);
+                    registers[*dst as usize] = match op {
+                        Operator::And => a & b,
+                        Operator::Or => a | b,
+                        Operator::Xor => a ^ b,
+                        Operator::Iff => !(a ^ b),
+                        Operator::Implies => !a | b,
+                    };
+                }
+            }
+        }
+        registers[0]
+    }
+
+    /// Value of the formula where variable `i` has the value `values[i]`.
+    ///
+    /// # Panics
+    ///
+    /// If there are fewer values than variables.
+    pub fn evaluate(&self, values: &[bool]) -> bool {
+        assert!(
+            values.len() >= self.variables.len(),
+            "{} values given for {} variables",
+            values.len(),
+            self.variables.len()
+        );
+        self.run(|i| values[i] as u64) & 1 == 1
+    }
+
+    /// Value of the formula where variable `i` has the value of bit `i` of `mask`, and
+    /// variables past the 64th are false.
+    pub fn evaluate_mask(&self, mask: u64) -> bool {
+        self.run(|i| mask.checked_shr(i as u32).unwrap_or(0)) & 1 == 1
+    }
+
+    /// Values of the formula on 64 assignments at once: bit `j` of the result is the value
+    /// where variable `i` has the value of bit `j` of `columns[i]`.
+    ///
+    /// # Panics
+    ///
+    /// If there are fewer columns than variables.
+    pub fn evaluate_columns(&self, columns: &[u64]) -> u64 {
+        assert!(
+            columns.len() >= self.variables.len(),
+            "{} columns given for {} variables",
+            columns.len(),
+            self.variables.len()
+        );
+        self.run(|i| columns[i])
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Constant { dst, value } => write!(f, "r{} = {}", dst, *value as u8),
+            Instruction::Load { dst, variable } => write!(f, "r{} = v{}", dst, variable),
+            Instruction::Not { dst, src } => write!(f, "r{} = !r{}", dst, src),
+            Instruction::BinaryOperator {
+                op,
+                dst,
+                left,
+                right,
+            } => write!(f, "r{} = r{} {} r{}", dst, left, op, right),
+        }
+    }
+}
+
+impl AstNode {
+    /// The formula as a register program, see [`CompiledFormula`].
+    pub fn compile(&self) -> CompiledFormula {
+        CompiledFormula::new(self)
+    }
+}
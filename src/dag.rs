@@ -0,0 +1,269 @@
+use crate::ast::{AstNode, Operator};
+use crate::symbol::Symbol;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Index of a node in a [`Dag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(u32);
+
+/// A node whose children are nodes of the same [`Dag`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Node {
+    Constant(bool),
+    Variable(Symbol),
+    Not(NodeId),
+    BinaryOperator(Operator, NodeId, NodeId),
+}
+
+/// Arena of hash-consed formula nodes: structurally equal subformulas share one node, so
+/// two ids of the same arena are equal exactly when their formulas are identical.
+#[derive(Debug, Clone, Default)]
+pub struct Dag {
+    nodes: Vec<Node>,
+    ids: HashMap<Node, NodeId>,
+}
+
+// Clauses of a CNF, each a list of literal nodes
+type Clauses = Rc<Vec<Vec<NodeId>>>;
+
+impl Dag {
+    pub fn new() -> Dag {
+        Dag::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn node(&self, id: NodeId) -> &Node {
+        &self.nodes[id.0 as usize]
+    }
+
+    /// Id of the node, which is only added if no equal node exists.
+    pub fn intern(&mut self, node: Node) -> NodeId {
+        if let Some(&id) = self.ids.get(&node) {
+            return id;
+        }
+
+        let id = NodeId(self.nodes.len() as u32);
+        self.nodes.push(node.clone());
+        self.ids.insert(node, id);
+        id
+    }
+
+    pub fn constant(&mut self, value: bool) -> NodeId {
+        self.intern(Node::Constant(value))
+    }
+
+    pub fn variable(&mut self, name: Symbol) -> NodeId {
+        self.intern(Node::Variable(name))
+    }
+
+    pub fn not(&mut self, child: NodeId) -> NodeId {
+        self.intern(Node::Not(child))
+    }
+
+    pub fn binary(&mut self, op: Operator, left: NodeId, right: NodeId) -> NodeId {
+        self.intern(Node::BinaryOperator(op, left, right))
+    }
+
+    // Build `left ∧ right`, folding constant operands
+    fn conjunction(&mut self, left: NodeId, right: NodeId) -> NodeId {
+        match (self.node(left), self.node(right)) {
+            (Node::Constant(false), _) | (_, Node::Constant(false)) => self.constant(false),
+            (Node::Constant(true), _) => right,
+            (_, Node::Constant(true)) => left,
+            _ => self.binary(Operator::And, left, right),
+        }
+    }
+
+    // Build `left ∨ right`, folding constant operands
+    fn disjunction(&mut self, left: NodeId, right: NodeId) -> NodeId {
+        match (self.node(left), self.node(right)) {
+            (Node::Constant(true), _) | (_, Node::Constant(true)) => self.constant(true),
+            (Node::Constant(false), _) => right,
+            (_, Node::Constant(false)) => left,
+            _ => self.binary(Operator::Or, left, right),
+        }
+    }
+
+    /// Number of distinct nodes reachable from `id`, each shared node counted once.
+    pub fn size(&self, id: NodeId) -> usize {
+        let mut seen = HashSet::from([id]);
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let children = match self.node(id) {
+                Node::Constant(_) | Node::Variable(_) => vec![],
+                Node::Not(child) => vec![*child],
+                Node::BinaryOperator(_, left, right) => vec![*left, *right],
+            };
+            for child in children {
+                if seen.insert(child) {
+                    stack.push(child);
+                }
+            }
+        }
+        seen.len()
+    }
+
+    /// The formula of a node as a tree, in which shared nodes are copied.
+    pub fn to_ast(&self, id: NodeId) -> AstNode {
+        fn go(dag: &Dag, id: NodeId, memo: &mut HashMap<NodeId, AstNode>) -> AstNode {
+            if let Some(ast) = memo.get(&id) {
+                return ast.clone();
+            }
+            let ast = match dag.node(id) {
+                Node::Constant(value) => AstNode::Constant(*value),
+                Node::Variable(name) => AstNode::Variable(*name),
+                Node::Not(child) => AstNode::Not(Box::new(go(dag, *child, memo))),
+                Node::BinaryOperator(op, left, right) => AstNode::BinaryOperator(
+                    op.clone(),
+                    Box::new(go(dag, *left, memo)),
+                    Box::new(go(dag, *right, memo)),
+                ),
+            };
+            memo.insert(id, ast.clone());
+            ast
+        }
+
+        go(self, id, &mut HashMap::new())
+    }
+
+    /// Negation normal form of a node with the same rewrites as [`AstNode::to_nnf`]. Each
+    /// node is converted once per polarity, however many times it is shared.
+    pub fn to_nnf(&mut self, id: NodeId) -> NodeId {
+        fn go(
+            dag: &mut Dag,
+            id: NodeId,
+            negated: bool,
+            memo: &mut HashMap<(NodeId, bool), NodeId>,
+        ) -> NodeId {
+            if let Some(&result) = memo.get(&(id, negated)) {
+                return result;
+            }
+
+            let result = match dag.node(id).clone() {
+                Node::Constant(value) => dag.constant(value != negated),
+                Node::Variable(_) if negated => dag.not(id),
+                Node::Variable(_) => id,
+                Node::Not(child) => go(dag, child, !negated, memo),
+                Node::BinaryOperator(op, left, right) => match op {
+                    // De Morgan's laws for the negated forms
+                    Operator::And | Operator::Or => {
+                        let a = go(dag, left, negated, memo);
+                        let b = go(dag, right, negated, memo);
+                        if (op == Operator::And) != negated {
+                            dag.conjunction(a, b)
+                        } else {
+                            dag.disjunction(a, b)
+                        }
+                    }
+                    // A → B == ¬A ∨ B and ¬(A → B) == A ∧ ¬B
+                    Operator::Implies => {
+                        let a = go(dag, left, !negated, memo);
+                        let b = go(dag, right, negated, memo);
+                        if negated {
+                            dag.conjunction(a, b)
+                        } else {
+                            dag.disjunction(a, b)
+                        }
+                    }
+                    // (A ∧ B) ∨ (¬A ∧ ¬B) when both sides must agree, otherwise
+                    // (A ∧ ¬B) ∨ (¬A ∧ B)
+                    Operator::Iff | Operator::Xor => {
+                        let agree = (op == Operator::Iff) != negated;
+                        let a = go(dag, left, false, memo);
+                        let not_a = go(dag, left, true, memo);
+                        let b = go(dag, right, !agree, memo);
+                        let not_b = go(dag, right, agree, memo);
+                        let first = dag.conjunction(a, b);
+                        let second = dag.conjunction(not_a, not_b);
+                        dag.disjunction(first, second)
+                    }
+                },
+            };
+            memo.insert((id, negated), result);
+            result
+        }
+
+        go(self, id, false, &mut HashMap::new())
+    }
+
+    /// Conjunctive normal form of a node, right-associated like [`AstNode::to_cnf`]. The
+    /// clauses of every node of the NNF are computed once and shared between its parents.
+    pub fn to_cnf(&mut self, id: NodeId) -> NodeId {
+        fn clauses(dag: &Dag, id: NodeId, memo: &mut HashMap<NodeId, Clauses>) -> Clauses {
+            if let Some(result) = memo.get(&id) {
+                return result.clone();
+            }
+
+            let result: Clauses = match dag.node(id) {
+                Node::Constant(true) => Rc::new(vec![]),
+                Node::Constant(false) => Rc::new(vec![vec![]]),
+                Node::BinaryOperator(Operator::And, left, right) => {
+                    let left = clauses(dag, *left, memo);
+                    let right = clauses(dag, *right, memo);
+                    Rc::new(left.iter().chain(right.iter()).cloned().collect())
+                }
+                // (A ∧ B) ∨ C == (A ∨ C) ∧ (B ∨ C)
+                Node::BinaryOperator(Operator::Or, left, right) => {
+                    let left = clauses(dag, *left, memo);
+                    let right = clauses(dag, *right, memo);
+                    let mut product = Vec::with_capacity(left.len() * right.len());
+                    for a in left.iter() {
+                        for b in right.iter() {
+                            product.push(a.iter().chain(b).copied().collect());
+                        }
+                    }
+                    Rc::new(product)
+                }
+                // Literals
+                _ => Rc::new(vec![vec![id]]),
+            };
+            memo.insert(id, result.clone());
+            result
+        }
+
+        let nnf = self.to_nnf(id);
+        let clauses = clauses(self, nnf, &mut HashMap::new());
+
+        let clauses: Vec<NodeId> = clauses
+            .iter()
+            .map(|clause| self.right_fold(clause.clone(), Operator::Or, false))
+            .collect();
+        self.right_fold(clauses, Operator::And, true)
+    }
+
+    // Join `ids` with `op` into a right-leaning chain, `empty` when there is nothing to join
+    fn right_fold(&mut self, ids: Vec<NodeId>, op: Operator, empty: bool) -> NodeId {
+        let mut iter = ids.into_iter().rev();
+        match iter.next() {
+            Some(last) => iter.fold(last, |acc, id| self.binary(op.clone(), id, acc)),
+            None => self.constant(empty),
+        }
+    }
+}
+
+impl AstNode {
+    /// Id of the formula in `dag`, adding the nodes it does not already contain.
+    pub fn to_dag(&self, dag: &mut Dag) -> NodeId {
+        match self {
+            AstNode::Constant(value) => dag.constant(*value),
+            AstNode::Variable(name) => dag.variable(*name),
+            AstNode::Not(child) => {
+                let child = child.to_dag(dag);
+                dag.not(child)
+            }
+            AstNode::BinaryOperator(op, left, right) => {
+                let left = left.to_dag(dag);
+                let right = right.to_dag(dag);
+                dag.binary(op.clone(), left, right)
+            }
+        }
+    }
+}
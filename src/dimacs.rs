@@ -0,0 +1,207 @@
+use crate::ast::{AstNode, Operator};
+use crate::cnf::{Cnf, Lit};
+use crate::error::DimacsError;
+use crate::symbol::Symbol;
+use crate::tseitin::Mode;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+
+/// A clause set in the DIMACS `p cnf` format, with the names of the variables that
+/// correspond to formula variables.
+///
+/// Names are written as `c var <number> <name>` comments before the header and are read
+/// back from the same comments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dimacs {
+    pub cnf: Cnf,
+    pub names: HashMap<u32, Symbol>,
+}
+
+impl Dimacs {
+    /// Largest number of variables a header may declare, as every declared variable is
+    /// allocated by the solver whether clauses use it or not.
+    pub const MAX_VARIABLES: u32 = 1 << 24;
+
+    pub fn parse(input: &str) -> Result<Dimacs, DimacsError> {
+        let mut names = HashMap::new();
+        let mut numbers: HashMap<Symbol, u32> = HashMap::new();
+        let mut header: Option<(u64, usize)> = None;
+        let mut cnf = Cnf::new();
+        let mut clause = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+
+            if let Some(comment) = line.strip_prefix('c') {
+                let mut words = comment.split_whitespace();
+                if let (Some("var"), Some(number), Some(name)) =
+                    (words.next(), words.next(), words.next())
+                {
+                    if let Ok(number) = number.parse::<u32>() {
+                        if number > 0 {
+                            let (var, symbol) = (number - 1, Symbol::new(name));
+                            // Two variables with one name would merge into one symbol
+                            if numbers.get(&symbol).is_some_and(|&other| other != var) {
+                                return Err(DimacsError::DuplicateName {
+                                    line: line_number,
+                                    name: name.to_string(),
+                                });
+                            }
+                            numbers.insert(symbol, var);
+                            names.insert(var, symbol);
+                        }
+                    }
+                }
+                continue;
+            }
+
+            // Some benchmark collections end their files with `%`
+            if line.starts_with('%') {
+                break;
+            }
+
+            if line.starts_with('p') {
+                let words: Vec<&str> = line.split_whitespace().collect();
+                match words.as_slice() {
+                    ["p", "cnf", vars, clauses] if header.is_none() => {
+                        let vars = vars.parse::<u32>();
+                        let clauses = clauses.parse::<usize>();
+                        match (vars, clauses) {
+                            (Ok(vars), Ok(_)) if vars > Self::MAX_VARIABLES => {
+                                return Err(DimacsError::TooManyVariables {
+                                    line: line_number,
+                                    count: vars,
+                                });
+                            }
+                            (Ok(vars), Ok(clauses)) => {
+                                header = Some((vars as u64, clauses));
+                                cnf = Cnf::with_vars(vars);
+                            }
+                            _ => return Err(DimacsError::InvalidHeader { line: line_number }),
+                        }
+                    }
+                    _ => return Err(DimacsError::InvalidHeader { line: line_number }),
+                }
+                continue;
+            }
+
+            for token in line.split_whitespace() {
+                let Some((num_vars, _)) = header else {
+                    return Err(DimacsError::MissingHeader);
+                };
+                let n: i64 = token.parse().map_err(|_| DimacsError::InvalidLiteral {
+                    line: line_number,
+                    token: token.to_string(),
+                })?;
+
+                if n == 0 {
+                    cnf.add_clause(std::mem::take(&mut clause));
+                } else if n.unsigned_abs() > num_vars {
+                    return Err(DimacsError::VariableOutOfRange {
+                        line: line_number,
+                        var: n.unsigned_abs(),
+                    });
+                } else {
+                    clause.push(Lit::from_dimacs(n));
+                }
+            }
+        }
+
+        let Some((_, expected)) = header else {
+            return Err(DimacsError::MissingHeader);
+        };
+        if !clause.is_empty() {
+            return Err(DimacsError::UnterminatedClause);
+        }
+        if cnf.clauses().len() != expected {
+            return Err(DimacsError::ClauseCount {
+                expected,
+                found: cnf.clauses().len(),
+            });
+        }
+
+        Ok(Dimacs { cnf, names })
+    }
+
+    // Name of a variable in the AST. Unnamed variables are called `x<number>`, primed
+    // until the name differs from every name of the file.
+    fn name(&self, var: u32, taken: &HashSet<Symbol>) -> Symbol {
+        if let Some(name) = self.names.get(&var) {
+            return *name;
+        }
+
+        let mut name = format!("x{}", var + 1);
+        while taken.contains(&Symbol::new(&name)) {
+            name.push('\'');
+        }
+        Symbol::new(&name)
+    }
+
+    /// The clause set as a right-associated conjunction of right-associated disjunctions.
+    pub fn to_ast(&self) -> AstNode {
+        let taken: HashSet<Symbol> = self.names.values().copied().collect();
+        let literal = |lit: &Lit| {
+            let var = AstNode::Variable(self.name(lit.var(), &taken));
+            if lit.is_negated() {
+                AstNode::Not(Box::new(var))
+            } else {
+                var
+            }
+        };
+
+        let clauses: Vec<AstNode> = self
+            .cnf
+            .clauses()
+            .iter()
+            .map(|clause| {
+                let literals: Vec<AstNode> = clause.iter().map(literal).collect();
+                AstNode::right_fold(literals, Operator::Or, false)
+            })
+            .collect();
+
+        AstNode::right_fold(clauses, Operator::And, true)
+    }
+}
+
+impl fmt::Display for Dimacs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: BTreeMap<u32, Symbol> = self.names.iter().map(|(&k, &v)| (k, v)).collect();
+        for (var, name) in names {
+            writeln!(f, "c var {} {}", var + 1, name)?;
+        }
+
+        writeln!(
+            f,
+            "p cnf {} {}",
+            self.cnf.num_vars(),
+            self.cnf.clauses().len()
+        )?;
+        for clause in self.cnf.clauses() {
+            for lit in clause {
+                write!(f, "{} ", lit.to_dimacs())?;
+            }
+            writeln!(f, "0")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AstNode {
+    /// Equisatisfiable DIMACS encoding of the formula. Every formula variable keeps its
+    /// name through a `c var` comment, the other variables are Tseitin auxiliaries.
+    pub fn to_dimacs(&self) -> Dimacs {
+        let encoding = self.to_definitional_cnf(Mode::Tseitin);
+        let names = encoding
+            .inputs
+            .into_iter()
+            .map(|(name, var)| (var, name))
+            .collect();
+
+        Dimacs {
+            cnf: encoding.cnf,
+            names,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::ast::{AstNode, Operator};
+use crate::solver::Solution;
+use crate::symbol::Symbol;
+use std::collections::HashMap;
+
+/// Outcome of checking a relation between formulas.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Holds,
+    // An assignment to every variable of the formulas for which the relation fails
+    Counterexample(HashMap<Symbol, bool>),
+}
+
+impl Verdict {
+    pub fn holds(&self) -> bool {
+        matches!(self, Verdict::Holds)
+    }
+
+    pub fn counterexample(&self) -> Option<&HashMap<Symbol, bool>> {
+        match self {
+            Verdict::Holds => None,
+            Verdict::Counterexample(assignment) => Some(assignment),
+        }
+    }
+}
+
+// The relation holds when `witness`, the formula true exactly on its counterexamples,
+// is unsatisfiable
+fn refute(witness: &AstNode) -> Verdict {
+    match witness.solve() {
+        Solution::Sat(assignment) => Verdict::Counterexample(assignment),
+        Solution::Unsat => Verdict::Holds,
+    }
+}
+
+fn binary(op: Operator, left: &AstNode, right: &AstNode) -> AstNode {
+    AstNode::BinaryOperator(op, Box::new(left.clone()), Box::new(right.clone()))
+}
+
+impl AstNode {
+    /// Whether the formula is true under every assignment, otherwise one that makes it false.
+    pub fn is_tautology(&self) -> Verdict {
+        refute(&AstNode::Not(Box::new(self.clone())))
+    }
+
+    /// Whether the formula is false under every assignment, otherwise one that makes it true.
+    pub fn is_contradiction(&self) -> Verdict {
+        refute(self)
+    }
+
+    /// Whether every model of the formula is a model of `other`, otherwise an assignment
+    /// that makes the formula true and `other` false.
+    pub fn entails(&self, other: &AstNode) -> Verdict {
+        let negated = AstNode::Not(Box::new(other.clone()));
+        refute(&binary(Operator::And, self, &negated))
+    }
+
+    /// Whether both formulas have the same value under every assignment, otherwise one on
+    /// which they differ.
+    pub fn equivalent(&self, other: &AstNode) -> Verdict {
+        refute(&binary(Operator::Xor, self, other))
+    }
+}
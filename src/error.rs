@@ -0,0 +1,382 @@
+use crate::symbol::Symbol;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    // A character that is not part of the formula syntax
+    UnknownToken { pos: usize, token: char },
+    // An operator without enough operands on the stack
+    MissingOperand { pos: usize, operator: char },
+    // More than one operand left once the whole formula has been read
+    LeftoverOperands { pos: usize, count: usize },
+    // The formula contains nothing to parse
+    EmptyInput,
+    // A valid token in a place where the infix grammar does not allow it
+    UnexpectedToken { pos: usize, token: char },
+    // The infix formula ended in the middle of an expression
+    UnexpectedEnd { pos: usize },
+    // An opening parenthesis that is never closed
+    UnclosedParenthesis { pos: usize },
+    // A braced variable name that is empty or never closed
+    InvalidName { pos: usize },
+    // Parentheses, negations or right-associated operators nested beyond the parser's limit
+    TooDeep { pos: usize },
+}
+
+impl ParseError {
+    /// Byte offset in the formula where the error was detected, if any.
+    pub fn pos(&self) -> Option<usize> {
+        match self {
+            ParseError::UnknownToken { pos, .. }
+            | ParseError::MissingOperand { pos, .. }
+            | ParseError::LeftoverOperands { pos, .. }
+            | ParseError::UnexpectedToken { pos, .. }
+            | ParseError::UnexpectedEnd { pos }
+            | ParseError::UnclosedParenthesis { pos }
+            | ParseError::InvalidName { pos }
+            | ParseError::TooDeep { pos } => Some(*pos),
+            ParseError::EmptyInput => None,
+        }
+    }
+
+    /// Render the formula with a caret under the offending position, e.g.
+    ///
+    /// ```text
+    /// A&B
+    ///  ^ missing operand for operator '&'
+    /// ```
+    pub fn render(&self, formula: &str) -> String {
+        let message = self.message();
+        match self.pos() {
+            Some(pos) => {
+                // Offsets are in bytes but the caret must line up with characters
+                let column = formula
+                    .get(..pos)
+                    .map_or(formula.chars().count(), |prefix| prefix.chars().count());
+                format!("{}\n{}^ {}", formula, " ".repeat(column), message)
+            }
+            None => format!("{}\n{}", formula, message),
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            ParseError::UnknownToken { token, .. } => format!("unknown token '{}'", token),
+            ParseError::MissingOperand { operator, .. } => {
+                format!("missing operand for operator '{}'", operator)
+            }
+            ParseError::LeftoverOperands { count, .. } => {
+                format!("{} operands left without an operator", count)
+            }
+            ParseError::EmptyInput => "empty input".to_string(),
+            ParseError::UnexpectedToken { token, .. } => format!("unexpected token '{}'", token),
+            ParseError::UnexpectedEnd { .. } => "unexpected end of input".to_string(),
+            ParseError::UnclosedParenthesis { .. } => "unclosed parenthesis".to_string(),
+            ParseError::InvalidName { .. } => "invalid variable name".to_string(),
+            ParseError::TooDeep { .. } => "formula is nested too deeply".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pos() {
+            Some(pos) => write!(f, "{} at offset {}", self.message(), pos),
+            None => write!(f, "{}", self.message()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DimacsError {
+    // No `p cnf <variables> <clauses>` line before the first clause
+    MissingHeader,
+    // A `p` line that does not follow the `p cnf <variables> <clauses>` format
+    InvalidHeader { line: usize },
+    // A token in a clause that is not an integer
+    InvalidLiteral { line: usize, token: String },
+    // A literal whose variable is above the count declared in the header
+    VariableOutOfRange { line: usize, var: u64 },
+    // The number of clauses read differs from the header
+    ClauseCount { expected: usize, found: usize },
+    // The last clause is not terminated by 0
+    UnterminatedClause,
+    // A header declaring more variables than `Dimacs::MAX_VARIABLES`
+    TooManyVariables { line: usize, count: u32 },
+    // A `c var` comment giving a name already given to another variable
+    DuplicateName { line: usize, name: String },
+}
+
+impl fmt::Display for DimacsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DimacsError::MissingHeader => write!(f, "missing 'p cnf' header"),
+            DimacsError::InvalidHeader { line } => write!(f, "invalid header on line {}", line),
+            DimacsError::InvalidLiteral { line, token } => {
+                write!(f, "invalid literal '{}' on line {}", token, line)
+            }
+            DimacsError::VariableOutOfRange { line, var } => {
+                write!(f, "variable {} on line {} is not declared", var, line)
+            }
+            DimacsError::ClauseCount { expected, found } => {
+                write!(f, "expected {} clauses but found {}", expected, found)
+            }
+            DimacsError::UnterminatedClause => write!(f, "last clause is not terminated by 0"),
+            DimacsError::TooManyVariables { line, count } => {
+                write!(f, "header on line {} declares {} variables", line, count)
+            }
+            DimacsError::DuplicateName { line, name } => {
+                write!(f, "name '{}' on line {} is already taken", name, line)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DimacsError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaError {
+    // No `.i` or `.o` line before the first product term
+    MissingHeader,
+    // A directive with a missing or malformed argument
+    InvalidDirective { line: usize },
+    // A product term whose planes have the wrong width or contain an unknown character
+    InvalidTerm { line: usize },
+    // More inputs than a cube can hold
+    TooManyInputs { inputs: usize },
+    // The number of product terms read differs from the `.p` line
+    TermCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for PlaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaError::MissingHeader => write!(f, "missing '.i' or '.o' header"),
+            PlaError::InvalidDirective { line } => write!(f, "invalid directive on line {}", line),
+            PlaError::InvalidTerm { line } => write!(f, "invalid product term on line {}", line),
+            PlaError::TooManyInputs { inputs } => {
+                write!(f, "{} inputs exceed the limit of 64", inputs)
+            }
+            PlaError::TermCount { expected, found } => {
+                write!(f, "expected {} product terms but found {}", expected, found)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlaError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AigerError {
+    // The first line is not `aag M I L O A` or `aig M I L O A`
+    InvalidHeader,
+    // Latches make the circuit sequential, which formulas cannot express
+    Latches { count: u32 },
+    // An input, output, AND or symbol line that does not follow the format
+    InvalidLine { line: usize },
+    // A literal above the maximum variable index or never defined
+    UndefinedLiteral { literal: u32 },
+    // An input or AND gate whose variable is already an input or the output of a gate
+    Redefined { literal: u32 },
+    // The AND gates of the circuit depend on each other in a cycle
+    Cycle { literal: u32 },
+    // The file ends before every section declared in the header
+    UnexpectedEnd,
+    // A number of the binary format does not fit in 32 bits
+    Overflow,
+}
+
+impl fmt::Display for AigerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AigerError::InvalidHeader => write!(f, "invalid 'aag' or 'aig' header"),
+            AigerError::Latches { count } => {
+                write!(
+                    f,
+                    "{} latches found, only combinational circuits are supported",
+                    count
+                )
+            }
+            AigerError::InvalidLine { line } => write!(f, "invalid line {}", line),
+            AigerError::UndefinedLiteral { literal } => {
+                write!(f, "literal {} is not defined", literal)
+            }
+            AigerError::Redefined { literal } => {
+                write!(f, "literal {} is defined more than once", literal)
+            }
+            AigerError::Cycle { literal } => write!(f, "literal {} depends on itself", literal),
+            AigerError::UnexpectedEnd => write!(f, "unexpected end of file"),
+            AigerError::Overflow => write!(f, "number does not fit in 32 bits"),
+        }
+    }
+}
+
+impl std::error::Error for AigerError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    // The formula could not be parsed
+    Parse(ParseError),
+    // A variable of the formula has no value or set assigned to it
+    UnboundVariable(Symbol),
+    // The set bound to a variable has elements outside the universe
+    OutsideUniverse(Symbol),
+    // The number of sets given does not match the number of variables in the formula
+    SetCount {
+        variables: usize,
+        sets: usize,
+    },
+    // A bitset bound to a variable does not have one index per element of the set engine
+    BitsetCapacity {
+        variable: Symbol,
+        capacity: usize,
+        expected: usize,
+    },
+    // Two bitsets combined by an operation do not have the same capacity
+    CapacityMismatch {
+        capacity: usize,
+        other: usize,
+    },
+    // A DIMACS file could not be read
+    Dimacs(DimacsError),
+    // A PLA file could not be read
+    Pla(PlaError),
+    // An AIGER file could not be read
+    Aiger(AigerError),
+    // Karnaugh maps are only drawn for 2 to 6 variables
+    MapSize {
+        variables: usize,
+    },
+    // Arithmetic circuits are only built for operands of 1 to 32 bits
+    OperandWidth {
+        width: u32,
+    },
+    // The formula has more variables than an algorithm can enumerate or represent
+    TooManyVariables {
+        variables: usize,
+        limit: usize,
+    },
+    // A minterm with bits beyond its number of variables
+    MintermRange {
+        minterm: u64,
+        variables: usize,
+    },
+    // A truth table without one value per row
+    TableLength {
+        expected: usize,
+        found: usize,
+    },
+    // A hexadecimal truth table with the wrong number of digits for its variables
+    HexLength {
+        expected: usize,
+        found: usize,
+    },
+    // A character that is not a hexadecimal digit, or a digit with bits past the last row,
+    // at a byte offset of the input
+    InvalidHex {
+        pos: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Parse(err) => write!(f, "parse error: {}", err),
+            Error::Dimacs(err) => write!(f, "DIMACS error: {}", err),
+            Error::Pla(err) => write!(f, "PLA error: {}", err),
+            Error::Aiger(err) => write!(f, "AIGER error: {}", err),
+            Error::MapSize { variables } => write!(
+                f,
+                "Karnaugh maps need 2 to 6 variables but the formula has {}",
+                variables
+            ),
+            Error::OperandWidth { width } => {
+                write!(f, "operands need 1 to 32 bits but {} were given", width)
+            }
+            Error::TooManyVariables { variables, limit } => write!(
+                f,
+                "formula has {} variables but at most {} are supported",
+                variables, limit
+            ),
+            Error::MintermRange { minterm, variables } => write!(
+                f,
+                "minterm {} is out of range for {} variables",
+                minterm, variables
+            ),
+            Error::TableLength { expected, found } => write!(
+                f,
+                "truth table needs {} values but {} were given",
+                expected, found
+            ),
+            Error::HexLength { expected, found } => write!(
+                f,
+                "truth table needs {} hexadecimal digits but {} were given",
+                expected, found
+            ),
+            Error::InvalidHex { pos } => write!(f, "invalid hexadecimal digit at offset {}", pos),
+            Error::UnboundVariable(var) => write!(f, "variable '{}' is not bound", var),
+            Error::OutsideUniverse(var) => write!(
+                f,
+                "set bound to variable '{}' has elements outside the universe",
+                var
+            ),
+            Error::SetCount { variables, sets } => write!(
+                f,
+                "formula has {} variables but {} sets were given",
+                variables, sets
+            ),
+            Error::BitsetCapacity {
+                variable,
+                capacity,
+                expected,
+            } => write!(
+                f,
+                "bitset bound to variable '{}' has capacity {} but the engine has {} elements",
+                variable, capacity, expected
+            ),
+            Error::CapacityMismatch { capacity, other } => write!(
+                f,
+                "bitsets of capacities {} and {} cannot be combined",
+                capacity, other
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Parse(err) => Some(err),
+            Error::Dimacs(err) => Some(err),
+            Error::Pla(err) => Some(err),
+            Error::Aiger(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Self {
+        Error::Parse(err)
+    }
+}
+
+impl From<DimacsError> for Error {
+    fn from(err: DimacsError) -> Self {
+        Error::Dimacs(err)
+    }
+}
+
+impl From<AigerError> for Error {
+    fn from(err: AigerError) -> Self {
+        Error::Aiger(err)
+    }
+}
+
+impl From<PlaError> for Error {
+    fn from(err: PlaError) -> Self {
+        Error::Pla(err)
+    }
+}
@@ -0,0 +1,398 @@
+use crate::ast::{AstNode, Operator};
+use crate::bdd::BddManager;
+use crate::error::Error;
+use crate::qm::{sort_cover, Implicant};
+use crate::symbol::Symbol;
+use std::collections::HashMap;
+
+// Mask of the bits used by `num_vars` variables
+fn full(num_vars: usize) -> u64 {
+    if num_vars == 64 {
+        u64::MAX
+    } else {
+        (1 << num_vars) - 1
+    }
+}
+
+fn universe(num_vars: usize) -> Implicant {
+    Implicant {
+        value: 0,
+        mask: full(num_vars),
+    }
+}
+
+// Cube fixing the variable of `bit` to `value`
+fn literal(num_vars: usize, bit: u64, value: bool) -> Implicant {
+    Implicant {
+        value: if value { bit } else { 0 },
+        mask: full(num_vars) & !bit,
+    }
+}
+
+fn intersects(a: &Implicant, b: &Implicant) -> bool {
+    (a.value ^ b.value) & !a.mask & !b.mask == 0
+}
+
+// Whether every minterm of `b` is in `a`
+fn contains(a: &Implicant, b: &Implicant) -> bool {
+    b.mask & !a.mask == 0 && (a.value ^ b.value) & !a.mask == 0
+}
+
+// Smallest cube containing every cube of `cubes`
+fn supercube(cubes: &[Implicant]) -> Implicant {
+    let mask = cubes.iter().fold(0, |acc, c| acc | c.mask);
+    let ones = cubes.iter().fold(0, |acc, c| acc | c.value);
+    let zeros = cubes.iter().fold(0, |acc, c| acc | !c.value & !c.mask);
+    let mask = mask | (ones & zeros);
+    Implicant {
+        value: ones & !mask,
+        mask,
+    }
+}
+
+// Cofactor of the cover with respect to the cube `p`: the cubes meeting `p` with the
+// variables fixed by `p` freed
+fn cofactor(cover: &[Implicant], p: &Implicant, num_vars: usize) -> Vec<Implicant> {
+    let freed = full(num_vars) & !p.mask;
+    cover
+        .iter()
+        .filter(|c| intersects(c, p))
+        .map(|c| Implicant {
+            value: c.value & !freed,
+            mask: c.mask | freed,
+        })
+        .collect()
+}
+
+// Variable to split on: the one fixed in the most cubes, preferring variables that appear
+// with both values. `None` when the cover is unate, or when no variable is fixed and
+// `binate_only` is false.
+fn split_variable(cover: &[Implicant], num_vars: usize, binate_only: bool) -> Option<u64> {
+    let mut best: Option<(bool, usize, u64)> = None;
+    for bit in (0..num_vars).map(|i| 1u64 << i) {
+        let ones = cover
+            .iter()
+            .filter(|c| c.mask & bit == 0 && c.value & bit != 0)
+            .count();
+        let zeros = cover
+            .iter()
+            .filter(|c| c.mask & bit == 0 && c.value & bit == 0)
+            .count();
+        let binate = ones > 0 && zeros > 0;
+        if ones + zeros == 0 || (binate_only && !binate) {
+            continue;
+        }
+        let key = (binate, ones + zeros, bit);
+        if best.map_or(true, |best| (key.0, key.1) > (best.0, best.1)) {
+            best = Some(key);
+        }
+    }
+    best.map(|(_, _, bit)| bit)
+}
+
+// Whether the cover contains every minterm
+fn tautology(cover: &[Implicant], num_vars: usize) -> bool {
+    if cover.iter().any(|c| c.mask == full(num_vars)) {
+        return true;
+    }
+    // A unate cover is a tautology only if it contains the universe
+    let Some(bit) = split_variable(cover, num_vars, true) else {
+        return false;
+    };
+
+    [true, false].iter().all(|&value| {
+        let cofactor = cofactor(cover, &literal(num_vars, bit, value), num_vars);
+        tautology(&cofactor, num_vars)
+    })
+}
+
+// Whether every minterm of `cube` is in the cover
+fn covered(cube: &Implicant, cover: &[Implicant], num_vars: usize) -> bool {
+    tautology(&cofactor(cover, cube, num_vars), num_vars)
+}
+
+// Remove the cubes contained in another cube of the cover
+fn single_cube_containment(cover: &mut Vec<Implicant>) {
+    cover.sort_by_key(|c| std::cmp::Reverse(c.mask.count_ones()));
+    cover.dedup();
+    let mut kept: Vec<Implicant> = Vec::with_capacity(cover.len());
+    for c in cover.drain(..) {
+        if !kept.iter().any(|k| contains(k, &c)) {
+            kept.push(c);
+        }
+    }
+    *cover = kept;
+}
+
+// Cubes hold their variables in the bits of a u64
+fn check_vars(num_vars: usize) -> Result<(), Error> {
+    if num_vars > 64 {
+        return Err(Error::TooManyVariables {
+            variables: num_vars,
+            limit: 64,
+        });
+    }
+    Ok(())
+}
+
+/// Cover of the minterms outside `cover` over `num_vars` variables, at most 64, computed by
+/// recursive Shannon expansion.
+pub fn complement(num_vars: usize, cover: &[Implicant]) -> Result<Vec<Implicant>, Error> {
+    check_vars(num_vars)?;
+    Ok(complement_cover(num_vars, cover))
+}
+
+fn complement_cover(num_vars: usize, cover: &[Implicant]) -> Vec<Implicant> {
+    if cover.is_empty() {
+        return vec![universe(num_vars)];
+    }
+    if cover.iter().any(|c| c.mask == full(num_vars)) {
+        return Vec::new();
+    }
+    if let [cube] = cover {
+        // De Morgan: one cube per negated literal
+        return (0..num_vars)
+            .map(|i| 1u64 << i)
+            .filter(|bit| cube.mask & bit == 0)
+            .map(|bit| literal(num_vars, bit, cube.value & bit == 0))
+            .collect();
+    }
+
+    let bit =
+        split_variable(cover, num_vars, false).expect("A cover without universe fixes a variable");
+    let high = complement_cover(
+        num_vars,
+        &cofactor(cover, &literal(num_vars, bit, true), num_vars),
+    );
+    let low = complement_cover(
+        num_vars,
+        &cofactor(cover, &literal(num_vars, bit, false), num_vars),
+    );
+
+    // Cubes found on both sides do not depend on the variable
+    let mut result = Vec::with_capacity(high.len() + low.len());
+    for c in &high {
+        if low.contains(c) {
+            result.push(*c);
+        } else {
+            result.push(Implicant {
+                value: c.value | bit,
+                mask: c.mask & !bit,
+            });
+        }
+    }
+    for c in low.iter().filter(|c| !high.contains(c)) {
+        result.push(Implicant {
+            value: c.value,
+            mask: c.mask & !bit,
+        });
+    }
+    single_cube_containment(&mut result);
+    result
+}
+
+// Raise the literals of every cube as long as it stays disjoint from the off-set, then drop
+// the cubes the expanded ones contain
+fn expand(cover: &[Implicant], off: &[Implicant], num_vars: usize) -> Vec<Implicant> {
+    let mut cubes = cover.to_vec();
+    // Largest cubes first, they are the most likely to absorb others
+    cubes.sort_by_key(|c| std::cmp::Reverse(c.mask.count_ones()));
+
+    let mut result: Vec<Implicant> = Vec::new();
+    for mut cube in cubes.clone() {
+        if result.iter().any(|r| contains(r, &cube)) {
+            continue;
+        }
+
+        // Raise first the literals that the fewest off-set cubes depend on, then those that
+        // absorb the most cubes not covered yet
+        let mut bits: Vec<u64> = (0..num_vars)
+            .map(|i| 1u64 << i)
+            .filter(|bit| cube.mask & bit == 0)
+            .collect();
+        bits.sort_by_key(|&bit| {
+            let raised = Implicant {
+                value: cube.value & !bit,
+                mask: cube.mask | bit,
+            };
+            let blocking = off.iter().filter(|r| intersects(&raised, r)).count();
+            let absorbed = cubes
+                .iter()
+                .filter(|c| contains(&raised, c) && !result.iter().any(|r| contains(r, c)))
+                .count();
+            (blocking, std::cmp::Reverse(absorbed))
+        });
+
+        for bit in bits {
+            let raised = Implicant {
+                value: cube.value & !bit,
+                mask: cube.mask | bit,
+            };
+            if !off.iter().any(|r| intersects(&raised, r)) {
+                cube = raised;
+            }
+        }
+
+        result.retain(|r| !contains(&cube, r));
+        result.push(cube);
+    }
+    result
+}
+
+// Drop the cubes covered by the rest of the cover and the don't-care set, smallest first
+fn irredundant(cover: &[Implicant], dont_care: &[Implicant], num_vars: usize) -> Vec<Implicant> {
+    let mut cubes = cover.to_vec();
+    cubes.sort_by_key(|c| c.mask.count_ones());
+
+    let mut i = 0;
+    while i < cubes.len() {
+        let others: Vec<Implicant> = cubes
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, c)| *c)
+            .chain(dont_care.iter().copied())
+            .collect();
+        if covered(&cubes[i], &others, num_vars) {
+            cubes.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    cubes
+}
+
+// Shrink every cube to the smallest cube that still covers the minterms no other cube
+// covers, so the next expansion can grow it in a different direction
+fn reduce(cover: &[Implicant], dont_care: &[Implicant], num_vars: usize) -> Vec<Implicant> {
+    let mut cubes = cover.to_vec();
+    cubes.sort_by_key(|c| std::cmp::Reverse(c.mask.count_ones()));
+
+    let mut i = 0;
+    while i < cubes.len() {
+        let cube = cubes[i];
+        let others: Vec<Implicant> = cubes
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, c)| *c)
+            .chain(dont_care.iter().copied())
+            .collect();
+        let uncovered = complement_cover(num_vars, &cofactor(&others, &cube, num_vars));
+
+        if uncovered.is_empty() {
+            cubes.remove(i);
+            continue;
+        }
+        let reduced = supercube(&uncovered);
+        let mask = cube.mask & reduced.mask;
+        cubes[i] = Implicant {
+            value: (cube.value | reduced.value) & !mask,
+            mask,
+        };
+        i += 1;
+    }
+    cubes
+}
+
+fn cost(cover: &[Implicant], num_vars: usize) -> (usize, usize) {
+    let literals = cover.iter().map(|c| c.literal_count(num_vars)).sum();
+    (cover.len(), literals)
+}
+
+/// Heuristic minimization of the function with on-set `on` and don't-care set `dont_care`
+/// over `num_vars` variables, at most 64.
+///
+/// Follows the expand, irredundant, reduce loop of Espresso until a pass no longer lowers the
+/// number of cubes or literals. Every cube of the result is prime, and no cube can be removed,
+/// but the cover need not be minimum.
+pub fn minimize(
+    num_vars: usize,
+    on: &[Implicant],
+    dont_care: &[Implicant],
+) -> Result<Vec<Implicant>, Error> {
+    check_vars(num_vars)?;
+    let care: Vec<Implicant> = on.iter().chain(dont_care).copied().collect();
+    let off = complement_cover(num_vars, &care);
+    minimize_with_off(num_vars, on, dont_care, &off)
+}
+
+/// Same as [`minimize`] with an off-set already at hand.
+pub fn minimize_with_off(
+    num_vars: usize,
+    on: &[Implicant],
+    dont_care: &[Implicant],
+    off: &[Implicant],
+) -> Result<Vec<Implicant>, Error> {
+    check_vars(num_vars)?;
+    let mut cover = expand(on, off, num_vars);
+    cover = irredundant(&cover, dont_care, num_vars);
+    let mut best = cost(&cover, num_vars);
+
+    loop {
+        let reduced = reduce(&cover, dont_care, num_vars);
+        let expanded = expand(&reduced, off, num_vars);
+        let candidate = irredundant(&expanded, dont_care, num_vars);
+        let candidate_cost = cost(&candidate, num_vars);
+        if candidate_cost >= best {
+            break;
+        }
+        cover = candidate;
+        best = candidate_cost;
+    }
+
+    sort_cover(&mut cover, num_vars);
+    Ok(cover)
+}
+
+impl AstNode {
+    /// Sorted variables of the formula and a cover of its on-set by disjoint cubes, read off
+    /// its BDD so that no truth table is built. A cube holds at most 64 variables.
+    pub fn to_cover(&self) -> Result<(Vec<Symbol>, Vec<Implicant>), Error> {
+        let mut variables: Vec<Symbol> = self.get_variables().into_iter().collect();
+        variables.sort();
+        let n = variables.len();
+        if n > 64 {
+            return Err(Error::TooManyVariables {
+                variables: n,
+                limit: 64,
+            });
+        }
+
+        let bits: HashMap<Symbol, u64> = variables
+            .iter()
+            .enumerate()
+            .map(|(i, &name)| (name, 1 << (n - 1 - i)))
+            .collect();
+        let mut manager = BddManager::with_order(&variables);
+        let f = self.to_bdd(&mut manager);
+        let cover = manager
+            .cubes(f)
+            .iter()
+            .map(|cube| {
+                cube.iter()
+                    .fold(universe(n), |term, (name, &value)| Implicant {
+                        value: if value {
+                            term.value | bits[name]
+                        } else {
+                            term.value
+                        },
+                        mask: term.mask & !bits[name],
+                    })
+            })
+            .collect();
+
+        Ok((variables, cover))
+    }
+
+    /// Sum of products of the formula minimized by [`minimize`].
+    pub fn espresso(&self) -> Result<AstNode, Error> {
+        let (variables, cover) = self.to_cover()?;
+        let cover = minimize(variables.len(), &cover, &[])?;
+        let products = cover
+            .iter()
+            .map(|term| term.to_product(&variables))
+            .collect();
+        Ok(AstNode::right_fold(products, Operator::Or, false))
+    }
+}
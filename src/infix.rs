@@ -0,0 +1,265 @@
+use crate::ast::{AstNode, Operator};
+use crate::error::ParseError;
+use crate::symbol::Symbol;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Constant(bool),
+    Variable(Symbol),
+    Not,
+    Binary(Operator),
+    LParen,
+    RParen,
+}
+
+impl Operator {
+    // Binding power of a binary operator in infix notation, higher binds tighter:
+    // ¬ > ∧ > ∨ > ⊕ > → > ↔
+    fn precedence(&self) -> u8 {
+        match self {
+            Operator::And => 5,
+            Operator::Or => 4,
+            Operator::Xor => 3,
+            Operator::Implies => 2,
+            Operator::Iff => 1,
+        }
+    }
+
+    fn is_right_associative(&self) -> bool {
+        matches!(self, Operator::Implies)
+    }
+}
+
+struct Lexer<'a> {
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(input: &'a str) -> Self {
+        Lexer {
+            chars: input.char_indices().peekable(),
+        }
+    }
+
+    // Consume `expected` after the first character of a multi-character arrow at `start`
+    fn expect(&mut self, expected: char, start: usize, first: char) -> Result<(), ParseError> {
+        match self.chars.next_if(|&(_, ch)| ch == expected) {
+            Some(_) => Ok(()),
+            None => Err(ParseError::UnknownToken {
+                pos: start,
+                token: first,
+            }),
+        }
+    }
+
+    // Read the rest of an identifier such as `x_12` or `bus[3]`, starting with `first`
+    fn identifier(&mut self, start: usize, first: char) -> Result<String, ParseError> {
+        let mut name = String::from(first);
+        while let Some((_, ch)) = self
+            .chars
+            .next_if(|&(_, ch)| ch.is_ascii_alphanumeric() || ch == '_')
+        {
+            name.push(ch);
+        }
+
+        // Optional index suffix
+        if self.chars.next_if(|&(_, ch)| ch == '[').is_some() {
+            name.push('[');
+            while let Some((_, ch)) = self.chars.next_if(|&(_, ch)| ch.is_ascii_digit()) {
+                name.push(ch);
+            }
+            if name.ends_with('[') || self.chars.next_if(|&(_, ch)| ch == ']').is_none() {
+                return Err(ParseError::InvalidName { pos: start });
+            }
+            name.push(']');
+        }
+
+        Ok(name)
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    // Tokens are paired with their byte offset and first character for error reporting
+    type Item = Result<(usize, char, Token), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((_, ch)) = self.chars.peek() {
+            if !ch.is_whitespace() {
+                break;
+            }
+            self.chars.next();
+        }
+
+        let (pos, ch) = self.chars.next()?;
+        let token = match ch {
+            '0' | '⊥' => Token::Constant(false),
+            '1' | '⊤' => Token::Constant(true),
+            _ if ch.is_ascii_alphabetic() || ch == '_' => match self.identifier(pos, ch) {
+                Ok(name) => Token::Variable(Symbol::new(&name)),
+                Err(e) => return Some(Err(e)),
+            },
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            '!' | '¬' => Token::Not,
+            '&' | '∧' => Token::Binary(Operator::And),
+            '|' | '∨' => Token::Binary(Operator::Or),
+            '^' | '⊕' => Token::Binary(Operator::Xor),
+            '→' => Token::Binary(Operator::Implies),
+            '↔' => Token::Binary(Operator::Iff),
+            // -> and <->
+            '-' => match self.expect('>', pos, ch) {
+                Ok(()) => Token::Binary(Operator::Implies),
+                Err(e) => return Some(Err(e)),
+            },
+            '<' => match self
+                .expect('-', pos, ch)
+                .and_then(|_| self.expect('>', pos, ch))
+            {
+                Ok(()) => Token::Binary(Operator::Iff),
+                Err(e) => return Some(Err(e)),
+            },
+            _ => return Some(Err(ParseError::UnknownToken { pos, token: ch })),
+        };
+
+        Some(Ok((pos, ch, token)))
+    }
+}
+
+// Nesting limit of parentheses, negations and right-hand operands, so that deep input is
+// reported as an error instead of overflowing the stack
+const MAX_DEPTH: usize = 1000;
+
+struct Parser {
+    tokens: Vec<(usize, char, Token)>,
+    pos: usize,
+    // Byte length of the formula, reported when the input ends too early
+    end: usize,
+    // Number of nested calls of `nested`
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, _, token)| token)
+    }
+
+    fn advance(&mut self) -> Option<(usize, char, Token)> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.tokens.get(self.pos) {
+            Some(&(pos, token, _)) => ParseError::UnexpectedToken { pos, token },
+            None => ParseError::UnexpectedEnd { pos: self.end },
+        }
+    }
+
+    // Run `parse` one nesting level deeper, the level being opened by the token at `start`
+    fn nested(
+        &mut self,
+        start: usize,
+        parse: impl FnOnce(&mut Parser) -> Result<AstNode, ParseError>,
+    ) -> Result<AstNode, ParseError> {
+        if self.depth == MAX_DEPTH {
+            return Err(ParseError::TooDeep { pos: start });
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    // Precedence climbing: parse operands joined by operators binding at least `min_prec`
+    fn parse_expression(&mut self, min_prec: u8) -> Result<AstNode, ParseError> {
+        let mut left = self.parse_unary()?;
+
+        while let Some(&(start, _, Token::Binary(ref op))) = self.tokens.get(self.pos) {
+            let op = op.clone();
+            let prec = op.precedence();
+            if prec < min_prec {
+                break;
+            }
+            self.advance();
+
+            let next_prec = if op.is_right_associative() {
+                prec
+            } else {
+                prec + 1
+            };
+            let right = self.nested(start, |parser| parser.parse_expression(next_prec))?;
+            left = AstNode::BinaryOperator(op, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<AstNode, ParseError> {
+        let Some((start, _, token)) = self.tokens.get(self.pos).cloned() else {
+            return Err(self.unexpected());
+        };
+
+        match token {
+            Token::Not => {
+                self.advance();
+                let operand = self.nested(start, |parser| parser.parse_unary())?;
+                Ok(AstNode::Not(Box::new(operand)))
+            }
+            Token::Constant(value) => {
+                self.advance();
+                Ok(AstNode::Constant(value))
+            }
+            Token::Variable(var) => {
+                self.advance();
+                Ok(AstNode::Variable(var))
+            }
+            Token::LParen => {
+                self.advance();
+                let inner = self.nested(start, |parser| parser.parse_expression(0))?;
+                match self.peek() {
+                    Some(Token::RParen) => {
+                        self.advance();
+                        Ok(inner)
+                    }
+                    Some(_) => Err(self.unexpected()),
+                    None => Err(ParseError::UnclosedParenthesis { pos: start }),
+                }
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+}
+
+impl AstNode {
+    /// Parse a formula written in conventional infix notation, e.g. `(A ∧ B) → ¬C`.
+    ///
+    /// Both ASCII (`&`, `|`, `^`, `!`, `->`, `<->`, `0`, `1`) and Unicode (`∧`, `∨`, `⊕`, `¬`,
+    /// `→`, `↔`, `⊥`, `⊤`) spellings are accepted. Precedence from tightest to loosest is ¬, ∧, ∨, ⊕, →, ↔;
+    /// implication is right-associative, every other binary operator is left-associative.
+    /// Variables are identifiers made of letters, digits and underscores, optionally
+    /// followed by an index, e.g. `enable`, `x_12` or `bus[3]`. Parentheses, negations and
+    /// right-hand operands nest at most 1000 deep.
+    pub fn from_infix(formula: &str) -> Result<AstNode, ParseError> {
+        let tokens = Lexer::new(formula).collect::<Result<Vec<_>, ParseError>>()?;
+        if tokens.is_empty() {
+            return Err(ParseError::EmptyInput);
+        }
+
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: formula.len(),
+            depth: 0,
+        };
+
+        let ast = parser.parse_expression(0)?;
+        if parser.peek().is_some() {
+            return Err(parser.unexpected());
+        }
+
+        Ok(ast)
+    }
+}
@@ -0,0 +1,169 @@
+use crate::ast::AstNode;
+use crate::boole::gray_code;
+use crate::error::Error;
+use crate::qm::{minimal_cover, Implicant};
+use crate::symbol::Symbol;
+use std::fmt;
+
+/// Karnaugh map of a formula of 2 to 6 variables.
+///
+/// The first half of the sorted variables, rounded down, indexes the rows and the rest the
+/// columns, both in Gray code order so that neighbouring cells differ in one variable. Up to
+/// four variables every group of a cover is a rectangle, wrapping around the edges; with
+/// five or six, the axes of three variables are also symmetric about their middle.
+#[derive(Debug, Clone)]
+pub struct KarnaughMap {
+    variables: Vec<Symbol>,
+    row_bits: usize,
+    col_bits: usize,
+    minterms: Vec<bool>,
+    groups: Vec<Implicant>,
+}
+
+impl KarnaughMap {
+    /// Map of the formula with the groups of a minimal sum of products.
+    pub fn new(ast: &AstNode) -> Result<KarnaughMap, Error> {
+        let n = ast.get_variables().len();
+        if !(2..=6).contains(&n) {
+            return Err(Error::MapSize { variables: n });
+        }
+
+        let table = ast.to_truth_table();
+        let groups = minimal_cover(n, &table.minterms(), &[])?;
+
+        Ok(KarnaughMap {
+            variables: table.variables().to_vec(),
+            row_bits: n / 2,
+            col_bits: n - n / 2,
+            minterms: (0..table.rows()).map(|row| table.value(row)).collect(),
+            groups,
+        })
+    }
+
+    /// The same map with other groups marked, such as those of [`crate::espresso::minimize`].
+    pub fn with_groups(self, groups: Vec<Implicant>) -> KarnaughMap {
+        KarnaughMap { groups, ..self }
+    }
+
+    pub fn variables(&self) -> &[Symbol] {
+        &self.variables
+    }
+
+    pub fn groups(&self) -> &[Implicant] {
+        &self.groups
+    }
+
+    pub fn rows(&self) -> usize {
+        1 << self.row_bits
+    }
+
+    pub fn columns(&self) -> usize {
+        1 << self.col_bits
+    }
+
+    /// Minterm shown in a cell.
+    pub fn minterm(&self, row: usize, column: usize) -> u64 {
+        let row = gray_code(row as u32) as u64;
+        let column = gray_code(column as u32) as u64;
+        row << self.col_bits | column
+    }
+
+    pub fn value(&self, row: usize, column: usize) -> bool {
+        self.minterms[self.minterm(row, column) as usize]
+    }
+
+    // Letter marking the n-th group
+    fn label(index: usize) -> char {
+        let letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+        letters[index % letters.len()]
+    }
+
+    // Names of some variables, run together when they are single letters
+    fn axis(names: &[Symbol]) -> String {
+        if names.iter().all(|name| name.is_letter()) {
+            names.iter().map(|name| name.as_str()).collect()
+        } else {
+            let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+            names.join(",")
+        }
+    }
+}
+
+impl fmt::Display for KarnaughMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits = |value: u64, width: usize| format!("{:0width$b}", value, width = width);
+
+        // Each cell holds the value followed by the labels of the groups covering it
+        let cells: Vec<Vec<String>> = (0..self.rows())
+            .map(|row| {
+                (0..self.columns())
+                    .map(|column| {
+                        let m = self.minterm(row, column);
+                        let value = if self.value(row, column) { '1' } else { '0' };
+                        let labels = self
+                            .groups
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, group)| group.covers(m))
+                            .map(|(index, _)| KarnaughMap::label(index));
+                        std::iter::once(value).chain(labels).collect()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let corner = format!(
+            "{}\\{}",
+            KarnaughMap::axis(&self.variables[..self.row_bits]),
+            KarnaughMap::axis(&self.variables[self.row_bits..])
+        );
+        let width = cells
+            .iter()
+            .flatten()
+            .map(|cell| cell.len())
+            .max()
+            .unwrap_or(0)
+            .max(self.col_bits);
+        let corner_width = corner.chars().count().max(self.row_bits);
+
+        let line = |label: String, cells: Vec<String>| {
+            let cells: String = cells
+                .iter()
+                .map(|cell| format!("  {:<w$}", cell, w = width))
+                .collect();
+            format!("{:>w$}{}", label, cells, w = corner_width)
+                .trim_end()
+                .to_string()
+        };
+
+        let labels = (0..self.columns())
+            .map(|column| bits(gray_code(column as u32) as u64, self.col_bits))
+            .collect();
+        writeln!(f, "{}", line(corner, labels))?;
+        for (row, cells) in cells.into_iter().enumerate() {
+            let label = bits(gray_code(row as u32) as u64, self.row_bits);
+            writeln!(f, "{}", line(label, cells))?;
+        }
+
+        // Legend with the product of every group
+        for (index, group) in self.groups.iter().enumerate() {
+            let literals: Vec<String> = group
+                .literals(&self.variables)
+                .map(|(name, positive)| {
+                    if positive {
+                        name.to_string()
+                    } else {
+                        format!("!{}", name)
+                    }
+                })
+                .collect();
+            let product = if literals.is_empty() {
+                "1".to_string()
+            } else {
+                literals.join(" & ")
+            };
+            writeln!(f, "{}: {}", KarnaughMap::label(index), product)?;
+        }
+        Ok(())
+    }
+}
@@ -1,2 +1,23 @@
+pub mod aig;
+pub mod anf;
 pub mod ast;
+pub mod bdd;
 pub mod boole;
+pub mod cnf;
+pub mod compiled;
+pub mod dag;
+pub mod dimacs;
+pub mod equivalence;
+pub mod error;
+pub mod espresso;
+pub mod infix;
+pub mod kmap;
+pub mod netlist;
+pub mod pla;
+pub mod qm;
+pub mod set_engine;
+pub mod simplify;
+pub mod solver;
+pub mod symbol;
+pub mod truth_table;
+pub mod tseitin;
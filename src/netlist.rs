@@ -0,0 +1,251 @@
+use crate::aig::{Aig, AigLit};
+use crate::ast::{AstNode, Operator};
+use crate::dag::{Dag, Node, NodeId};
+use crate::error::Error;
+use crate::symbol::Symbol;
+use std::collections::{HashMap, HashSet};
+
+/// Combinational circuit with two `width`-bit operands `a` and `b` (inputs `a0`, `b0`, ...
+/// least significant first) whose outputs share the nodes of one [`Dag`].
+///
+/// The circuits mirror [`adder`](crate::boole::adder) and
+/// [`multiplier`](crate::boole::multiplier): they produce every bit of the exact result
+/// that fits in a `u32`, so they agree with the native functions on all operands.
+#[derive(Debug, Clone)]
+pub struct Netlist {
+    dag: Dag,
+    inputs: Vec<Symbol>,
+    outputs: Vec<(Symbol, NodeId)>,
+}
+
+impl Netlist {
+    fn with_inputs(width: u32) -> Result<(Netlist, Vec<NodeId>, Vec<NodeId>), Error> {
+        if !(1..=32).contains(&width) {
+            return Err(Error::OperandWidth { width });
+        }
+        let mut netlist = Netlist {
+            dag: Dag::new(),
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        };
+        let a = netlist.operand('a', width);
+        let b = netlist.operand('b', width);
+        Ok((netlist, a, b))
+    }
+
+    fn operand(&mut self, prefix: char, width: u32) -> Vec<NodeId> {
+        (0..width)
+            .map(|i| {
+                let name = Symbol::new(&format!("{}{}", prefix, i));
+                self.inputs.push(name);
+                self.dag.variable(name)
+            })
+            .collect()
+    }
+
+    // Sum and carry of up to three bits, absent bits being zero
+    fn add_bits(&mut self, bits: &[Option<NodeId>]) -> (Option<NodeId>, Option<NodeId>) {
+        let bits: Vec<NodeId> = bits.iter().flatten().copied().collect();
+        match bits[..] {
+            [] => (None, None),
+            [x] => (Some(x), None),
+            // Half adder
+            [x, y] => (
+                Some(self.dag.binary(Operator::Xor, x, y)),
+                Some(self.dag.binary(Operator::And, x, y)),
+            ),
+            // Full adder
+            [x, y, carry] => {
+                let half = self.dag.binary(Operator::Xor, x, y);
+                let sum = self.dag.binary(Operator::Xor, half, carry);
+                let generate = self.dag.binary(Operator::And, x, y);
+                let propagate = self.dag.binary(Operator::And, half, carry);
+                (
+                    Some(sum),
+                    Some(self.dag.binary(Operator::Or, generate, propagate)),
+                )
+            }
+            _ => unreachable!("At most three bits are added"),
+        }
+    }
+
+    // Ripple-carry addition of two numbers, truncated to the length of `x`
+    fn ripple(&mut self, x: &[Option<NodeId>], y: &[Option<NodeId>]) -> Vec<Option<NodeId>> {
+        let mut carry = None;
+        let mut sum = Vec::with_capacity(x.len());
+        for (i, &bit) in x.iter().enumerate() {
+            let (s, c) = self.add_bits(&[bit, y.get(i).copied().flatten(), carry]);
+            sum.push(s);
+            carry = c;
+        }
+        sum
+    }
+
+    fn finish(mut self, prefix: char, bits: Vec<Option<NodeId>>) -> Netlist {
+        for (i, bit) in bits.into_iter().enumerate() {
+            let node = bit.unwrap_or_else(|| self.dag.constant(false));
+            self.outputs
+                .push((Symbol::new(&format!("{}{}", prefix, i)), node));
+        }
+        self
+    }
+
+    /// Ripple-carry adder of two `width`-bit numbers, with outputs `s0`, `s1`, ... The
+    /// carry out is the last output when it fits in 32 bits. Operands have 1 to 32 bits.
+    pub fn adder(width: u32) -> Result<Netlist, Error> {
+        let (mut netlist, a, b) = Netlist::with_inputs(width)?;
+        let bits = (width + 1).min(32) as usize;
+        let mut x: Vec<Option<NodeId>> = a.into_iter().map(Some).collect();
+        x.resize(bits, None);
+        let y: Vec<Option<NodeId>> = b.into_iter().map(Some).collect();
+
+        let sum = netlist.ripple(&x, &y);
+        Ok(netlist.finish('s', sum))
+    }
+
+    /// Shift-and-add array multiplier of two `width`-bit numbers, with outputs `p0`,
+    /// `p1`, ... up to `2 * width` bits or 32 bits. Row `i` adds `a & b_i` shifted by
+    /// `i` to the partial sum with a ripple-carry adder. Operands have 1 to 32 bits.
+    pub fn multiplier(width: u32) -> Result<Netlist, Error> {
+        let (mut netlist, a, b) = Netlist::with_inputs(width)?;
+        let bits = (2 * width).min(32) as usize;
+        let mut product: Vec<Option<NodeId>> = vec![None; bits];
+        for (i, &bi) in b.iter().enumerate().take(bits) {
+            let mut row = vec![None; i];
+            for &aj in a.iter().take(bits - i) {
+                row.push(Some(netlist.dag.binary(Operator::And, aj, bi)));
+            }
+            product = netlist.ripple(&product, &row);
+        }
+        Ok(netlist.finish('p', product))
+    }
+
+    pub fn dag(&self) -> &Dag {
+        &self.dag
+    }
+
+    /// Names of the inputs: the bits of `a`, then the bits of `b`.
+    pub fn inputs(&self) -> &[Symbol] {
+        &self.inputs
+    }
+
+    /// Names of the outputs and their nodes, least significant bit first.
+    pub fn outputs(&self) -> &[(Symbol, NodeId)] {
+        &self.outputs
+    }
+
+    /// Number of distinct operator nodes of the circuit, each shared gate counted once.
+    pub fn gate_count(&self) -> usize {
+        let mut seen = HashSet::new();
+        let mut stack: Vec<NodeId> = self.outputs.iter().map(|&(_, id)| id).collect();
+        let mut gates = 0;
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            match self.dag.node(id) {
+                Node::Constant(_) | Node::Variable(_) => {}
+                Node::Not(child) => {
+                    gates += 1;
+                    stack.push(*child);
+                }
+                Node::BinaryOperator(_, left, right) => {
+                    gates += 1;
+                    stack.extend([*left, *right]);
+                }
+            }
+        }
+        gates
+    }
+
+    /// Value of the inputs for the operands `a` and `b`, truncated to the width.
+    pub fn assignment(&self, a: u32, b: u32) -> HashMap<Symbol, bool> {
+        let width = self.inputs.len() / 2;
+        self.inputs
+            .iter()
+            .enumerate()
+            .map(|(i, &name)| {
+                let operand = if i < width { a } else { b };
+                (name, operand >> (i % width) & 1 == 1)
+            })
+            .collect()
+    }
+
+    /// Simulate the circuit on the operands `a` and `b`, truncated to the width.
+    pub fn evaluate(&self, a: u32, b: u32) -> u32 {
+        type Memo = HashMap<NodeId, bool>;
+        fn go(dag: &Dag, values: &HashMap<Symbol, bool>, id: NodeId, memo: &mut Memo) -> bool {
+            if let Some(&value) = memo.get(&id) {
+                return value;
+            }
+            let value = match dag.node(id) {
+                Node::Constant(value) => *value,
+                Node::Variable(name) => values[name],
+                Node::Not(child) => !go(dag, values, *child, memo),
+                Node::BinaryOperator(op, left, right) => {
+                    let (left, right) =
+                        (go(dag, values, *left, memo), go(dag, values, *right, memo));
+                    match op {
+                        Operator::And => left && right,
+                        Operator::Or => left || right,
+                        Operator::Xor => left ^ right,
+                        Operator::Iff => left == right,
+                        Operator::Implies => !left || right,
+                    }
+                }
+            };
+            memo.insert(id, value);
+            value
+        }
+
+        let values = self.assignment(a, b);
+        let mut memo = HashMap::new();
+        self.outputs
+            .iter()
+            .enumerate()
+            .fold(0, |result, (i, &(_, id))| {
+                result | (go(&self.dag, &values, id, &mut memo) as u32) << i
+            })
+    }
+
+    /// Formula of every output, in which shared gates are copied.
+    pub fn to_ast(&self) -> Vec<AstNode> {
+        self.outputs
+            .iter()
+            .map(|&(_, id)| self.dag.to_ast(id))
+            .collect()
+    }
+
+    /// The circuit as an And-Inverter Graph with the same inputs and outputs.
+    pub fn to_aig(&self) -> Aig {
+        fn go(dag: &Dag, id: NodeId, aig: &mut Aig, memo: &mut HashMap<NodeId, AigLit>) -> AigLit {
+            if let Some(&lit) = memo.get(&id) {
+                return lit;
+            }
+            let lit = match dag.node(id) {
+                Node::Constant(true) => AigLit::TRUE,
+                Node::Constant(false) => AigLit::FALSE,
+                Node::Variable(name) => aig.input(*name),
+                Node::Not(child) => !go(dag, *child, aig, memo),
+                Node::BinaryOperator(op, left, right) => {
+                    let left = go(dag, *left, aig, memo);
+                    let right = go(dag, *right, aig, memo);
+                    aig.apply(op, left, right)
+                }
+            };
+            memo.insert(id, lit);
+            lit
+        }
+
+        let mut aig = Aig::new();
+        for &name in &self.inputs {
+            aig.input(name);
+        }
+        let mut memo = HashMap::new();
+        for &(name, id) in &self.outputs {
+            let lit = go(&self.dag, id, &mut aig, &mut memo);
+            aig.add_output(name, lit);
+        }
+        aig
+    }
+}
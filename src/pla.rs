@@ -0,0 +1,240 @@
+use crate::ast::{AstNode, Operator};
+use crate::error::{Error, PlaError};
+use crate::espresso::{complement, minimize};
+use crate::qm::Implicant;
+use crate::symbol::Symbol;
+use std::fmt;
+
+/// A multiple-output function in the Berkeley PLA format read by Espresso.
+///
+/// Each output has an on-set and a don't-care set of cubes over the inputs. Files without
+/// `.ilb` or `.ob` lines get the names `x0, x1, ...` and `f0, f1, ...`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pla {
+    pub inputs: Vec<Symbol>,
+    pub outputs: Vec<Symbol>,
+    pub on: Vec<Vec<Implicant>>,
+    pub dont_care: Vec<Vec<Implicant>>,
+}
+
+fn names(prefix: &str, count: usize) -> Vec<Symbol> {
+    (0..count)
+        .map(|i| Symbol::new(&format!("{}{}", prefix, i)))
+        .collect()
+}
+
+impl Pla {
+    pub fn parse(input: &str) -> Result<Pla, PlaError> {
+        let mut num_inputs: Option<usize> = None;
+        let mut num_outputs: Option<usize> = None;
+        let mut inputs: Option<Vec<Symbol>> = None;
+        let mut outputs: Option<Vec<Symbol>> = None;
+        let mut expected: Option<usize> = None;
+        // Whether `0` in the output plane marks the off-set, as in the `fr` and `fdr` types
+        let mut has_off = false;
+        let mut terms = 0;
+        let mut on: Vec<Vec<Implicant>> = Vec::new();
+        let mut dont_care: Vec<Vec<Implicant>> = Vec::new();
+        let mut off: Vec<Vec<Implicant>> = Vec::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(directive) = line.strip_prefix('.') {
+                let invalid = PlaError::InvalidDirective { line: line_number };
+                let mut words = directive.split_whitespace();
+                let count = |word: Option<&str>| word.and_then(|w| w.parse::<usize>().ok());
+                match words.next() {
+                    Some("i") => {
+                        let n = count(words.next()).ok_or(invalid)?;
+                        if n > 64 {
+                            return Err(PlaError::TooManyInputs { inputs: n });
+                        }
+                        num_inputs = Some(n);
+                    }
+                    Some("o") => {
+                        let m = count(words.next()).ok_or(invalid)?;
+                        num_outputs = Some(m);
+                        on = vec![Vec::new(); m];
+                        dont_care = vec![Vec::new(); m];
+                        off = vec![Vec::new(); m];
+                    }
+                    Some("p") => expected = Some(count(words.next()).ok_or(invalid)?),
+                    Some("ilb") => {
+                        let list: Vec<Symbol> = words.map(Symbol::new).collect();
+                        if Some(list.len()) != num_inputs {
+                            return Err(invalid);
+                        }
+                        inputs = Some(list);
+                    }
+                    Some("ob") => {
+                        let list: Vec<Symbol> = words.map(Symbol::new).collect();
+                        if Some(list.len()) != num_outputs {
+                            return Err(invalid);
+                        }
+                        outputs = Some(list);
+                    }
+                    Some("type") => match words.next() {
+                        Some("f") | Some("fd") => has_off = false,
+                        Some("fr") | Some("fdr") => has_off = true,
+                        _ => return Err(invalid),
+                    },
+                    Some("e") | Some("end") => break,
+                    // Directives for other tools, such as `.phase` or `.pair`, are ignored
+                    _ => {}
+                }
+                continue;
+            }
+
+            let (Some(n), Some(m)) = (num_inputs, num_outputs) else {
+                return Err(PlaError::MissingHeader);
+            };
+            let invalid = PlaError::InvalidTerm { line: line_number };
+            // The planes may or may not be separated by whitespace
+            let chars: Vec<char> = line.chars().filter(|c| !c.is_whitespace()).collect();
+            if chars.len() != n + m {
+                return Err(invalid);
+            }
+
+            let mut cube = Implicant { value: 0, mask: 0 };
+            for (i, c) in chars[..n].iter().enumerate() {
+                let bit = 1 << (n - 1 - i);
+                match c {
+                    '0' => {}
+                    '1' => cube.value |= bit,
+                    '-' => cube.mask |= bit,
+                    _ => return Err(invalid),
+                }
+            }
+            for (o, c) in chars[n..].iter().enumerate() {
+                match c {
+                    '1' => on[o].push(cube),
+                    '-' | '2' => dont_care[o].push(cube),
+                    '0' if has_off => off[o].push(cube),
+                    '0' | '~' => {}
+                    _ => return Err(invalid),
+                }
+            }
+            terms += 1;
+        }
+
+        let (Some(n), Some(m)) = (num_inputs, num_outputs) else {
+            return Err(PlaError::MissingHeader);
+        };
+        if let Some(expected) = expected {
+            if expected != terms {
+                return Err(PlaError::TermCount {
+                    expected,
+                    found: terms,
+                });
+            }
+        }
+
+        // With an explicit off-set, everything outside the on-set and the off-set is free
+        if has_off {
+            for o in 0..m {
+                let care: Vec<Implicant> = on[o].iter().chain(&off[o]).copied().collect();
+                dont_care[o] = complement(n, &care).expect("A PLA has at most 64 inputs");
+            }
+        }
+
+        Ok(Pla {
+            inputs: inputs.unwrap_or_else(|| names("x", n)),
+            outputs: outputs.unwrap_or_else(|| names("f", m)),
+            on,
+            dont_care,
+        })
+    }
+
+    /// The same function with the on-set of every output minimized by Espresso, for at most
+    /// 64 inputs.
+    pub fn minimize(&self) -> Result<Pla, Error> {
+        let on = self
+            .on
+            .iter()
+            .zip(&self.dont_care)
+            .map(|(on, dont_care)| minimize(self.inputs.len(), on, dont_care))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Pla { on, ..self.clone() })
+    }
+
+    /// The sum of products of the on-set of every output, the don't-care set being
+    /// taken as false.
+    pub fn to_ast(&self) -> Vec<AstNode> {
+        self.on
+            .iter()
+            .map(|cover| {
+                let products = cover
+                    .iter()
+                    .map(|term| term.to_product(&self.inputs))
+                    .collect();
+                AstNode::right_fold(products, Operator::Or, false)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Pla {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let n = self.inputs.len();
+        let m = self.outputs.len();
+
+        // One row per distinct cube, with the outputs it belongs to
+        let mut rows: Vec<(Implicant, Vec<char>)> = Vec::new();
+        for (sets, symbol) in [(&self.on, '1'), (&self.dont_care, '-')] {
+            for (o, cover) in sets.iter().enumerate() {
+                for cube in cover {
+                    let index = match rows.iter().position(|(c, _)| c == cube) {
+                        Some(index) => index,
+                        None => {
+                            rows.push((*cube, vec!['0'; m]));
+                            rows.len() - 1
+                        }
+                    };
+                    if rows[index].1[o] == '0' {
+                        rows[index].1[o] = symbol;
+                    }
+                }
+            }
+        }
+
+        writeln!(f, ".i {}", n)?;
+        writeln!(f, ".o {}", m)?;
+        let inputs: Vec<&str> = self.inputs.iter().map(|s| s.as_str()).collect();
+        writeln!(f, ".ilb {}", inputs.join(" "))?;
+        let outputs: Vec<&str> = self.outputs.iter().map(|s| s.as_str()).collect();
+        writeln!(f, ".ob {}", outputs.join(" "))?;
+        writeln!(f, ".p {}", rows.len())?;
+        for (cube, outputs) in rows {
+            let inputs: String = (0..n)
+                .map(|i| 1u64 << (n - 1 - i))
+                .map(|bit| match (cube.mask & bit != 0, cube.value & bit != 0) {
+                    (true, _) => '-',
+                    (false, true) => '1',
+                    (false, false) => '0',
+                })
+                .collect();
+            let outputs: String = outputs.into_iter().collect();
+            writeln!(f, "{} {}", inputs, outputs)?;
+        }
+        writeln!(f, ".e")
+    }
+}
+
+impl AstNode {
+    /// Single-output PLA of the formula, with output `f` and the variables as inputs.
+    pub fn to_pla(&self) -> Result<Pla, Error> {
+        let (inputs, cover) = self.to_cover()?;
+        Ok(Pla {
+            inputs,
+            outputs: vec![Symbol::new("f")],
+            on: vec![cover],
+            dont_care: vec![Vec::new()],
+        })
+    }
+}
@@ -0,0 +1,310 @@
+use crate::ast::{AstNode, Operator};
+use crate::error::Error;
+use crate::symbol::Symbol;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Largest number of variables of a formula whose truth table is enumerated.
+pub const MAX_VARIABLES: usize = 20;
+
+/// A product term over `n` ordered variables: the bits set in `mask` are free and the others
+/// must equal the corresponding bits of `value`.
+///
+/// As for minterms, the first variable is the most significant bit, so minterm `m` assigns
+/// bit `n - 1 - i` of `m` to variable `i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Implicant {
+    pub value: u64,
+    pub mask: u64,
+}
+
+impl Implicant {
+    pub fn minterm(value: u64) -> Implicant {
+        Implicant { value, mask: 0 }
+    }
+
+    pub fn covers(&self, minterm: u64) -> bool {
+        minterm & !self.mask == self.value
+    }
+
+    /// Number of literals of the term over `num_vars` variables.
+    pub fn literal_count(&self, num_vars: usize) -> usize {
+        num_vars - self.mask.count_ones() as usize
+    }
+
+    // Literals of the term in variable order, as (variable, positive)
+    pub(crate) fn literals<'a>(
+        &self,
+        variables: &'a [Symbol],
+    ) -> impl Iterator<Item = (Symbol, bool)> + 'a {
+        let Implicant { value, mask } = *self;
+        let n = variables.len();
+        variables
+            .iter()
+            .enumerate()
+            .map(move |(i, &name)| (name, 1 << (n - 1 - i)))
+            .filter(move |(_, bit)| mask & bit == 0)
+            .map(move |(name, bit)| (name, value & bit != 0))
+    }
+
+    /// The term as a right-associated conjunction of literals.
+    pub fn to_product(&self, variables: &[Symbol]) -> AstNode {
+        let literals = self
+            .literals(variables)
+            .map(|(name, positive)| literal(name, positive))
+            .collect();
+        AstNode::right_fold(literals, Operator::And, true)
+    }
+
+    /// The complement of the term as a right-associated disjunction of literals, the clause
+    /// that excludes every minterm the term covers.
+    pub fn to_clause(&self, variables: &[Symbol]) -> AstNode {
+        let literals = self
+            .literals(variables)
+            .map(|(name, positive)| literal(name, !positive))
+            .collect();
+        AstNode::right_fold(literals, Operator::Or, false)
+    }
+}
+
+fn literal(name: Symbol, positive: bool) -> AstNode {
+    let var = AstNode::Variable(name);
+    if positive {
+        var
+    } else {
+        AstNode::Not(Box::new(var))
+    }
+}
+
+/// Every prime implicant of the function whose on-set is `minterms` and whose don't-care
+/// set is `dont_cares`, over `num_vars` variables, or an error if a term holds more than
+/// 64 variables or a minterm is out of range.
+pub fn prime_implicants(
+    num_vars: usize,
+    minterms: &[u64],
+    dont_cares: &[u64],
+) -> Result<Vec<Implicant>, Error> {
+    check_terms(num_vars, minterms.iter().chain(dont_cares))?;
+    let mut primes = BTreeSet::new();
+    let mut current: HashSet<Implicant> = minterms
+        .iter()
+        .chain(dont_cares)
+        .map(|&m| Implicant::minterm(m))
+        .collect();
+
+    // Merge pairs of terms that differ in a single fixed variable until nothing merges
+    while !current.is_empty() {
+        let mut merged = HashSet::new();
+        let mut used = HashSet::new();
+
+        for term in &current {
+            for bit in (0..num_vars).map(|i| 1u64 << i) {
+                if term.mask & bit != 0 || term.value & bit != 0 {
+                    continue;
+                }
+                let partner = Implicant {
+                    value: term.value | bit,
+                    mask: term.mask,
+                };
+                if current.contains(&partner) {
+                    merged.insert(Implicant {
+                        value: term.value,
+                        mask: term.mask | bit,
+                    });
+                    used.insert(*term);
+                    used.insert(partner);
+                }
+            }
+        }
+
+        primes.extend(current.difference(&used).copied());
+        current = merged;
+    }
+
+    Ok(primes.into_iter().collect())
+}
+
+/// A cover of `minterms` by prime implicants with the fewest terms, ties broken by the
+/// fewest literals. Essential primes are taken first and the remaining minterms are
+/// covered exactly with Petrick's method. The terms are checked as by [`prime_implicants`].
+pub fn minimal_cover(
+    num_vars: usize,
+    minterms: &[u64],
+    dont_cares: &[u64],
+) -> Result<Vec<Implicant>, Error> {
+    let primes = prime_implicants(num_vars, minterms, dont_cares)?;
+    let minterms: BTreeSet<u64> = minterms.iter().copied().collect();
+
+    // Primes covering each minterm
+    let coverage: HashMap<u64, Vec<usize>> = minterms
+        .iter()
+        .map(|&m| {
+            let covering = (0..primes.len()).filter(|&p| primes[p].covers(m)).collect();
+            (m, covering)
+        })
+        .collect();
+
+    let mut chosen: BTreeSet<usize> = coverage
+        .values()
+        .filter(|covering| covering.len() == 1)
+        .map(|covering| covering[0])
+        .collect();
+    let remaining: Vec<u64> = minterms
+        .iter()
+        .copied()
+        .filter(|&m| !chosen.iter().any(|&p| primes[p].covers(m)))
+        .collect();
+
+    // Petrick's method: multiply out the product over the remaining minterms of the sum of
+    // the primes covering them, keeping only the products that are not absorbed by another
+    let mut products: Vec<BTreeSet<usize>> = vec![BTreeSet::new()];
+    for m in &remaining {
+        let covering = &coverage[m];
+        let mut expanded: Vec<BTreeSet<usize>> = Vec::new();
+        for product in &products {
+            if covering.iter().any(|p| product.contains(p)) {
+                expanded.push(product.clone());
+                continue;
+            }
+            for &p in covering {
+                let mut product = product.clone();
+                product.insert(p);
+                expanded.push(product);
+            }
+        }
+        expanded.sort_by_key(|product| product.len());
+        expanded.dedup();
+
+        products.clear();
+        for product in expanded {
+            if !products.iter().any(|kept| kept.is_subset(&product)) {
+                products.push(product);
+            }
+        }
+    }
+
+    let cost = |product: &BTreeSet<usize>| {
+        let literals: usize = product
+            .iter()
+            .map(|&p| primes[p].literal_count(num_vars))
+            .sum();
+        (product.len(), literals)
+    };
+    if let Some(best) = products.into_iter().min_by_key(cost) {
+        chosen.extend(best);
+    }
+
+    let mut cover: Vec<Implicant> = chosen.into_iter().map(|p| primes[p]).collect();
+    sort_cover(&mut cover, num_vars);
+    Ok(cover)
+}
+
+// Terms in reading order: a positive literal, then a negative one, then a free variable
+pub(crate) fn sort_cover(cover: &mut [Implicant], num_vars: usize) {
+    cover.sort_by_key(|term| {
+        (0..num_vars)
+            .rev()
+            .map(|i| 1u64 << i)
+            .map(|bit| (term.mask & bit != 0, term.value & bit == 0))
+            .collect::<Vec<_>>()
+    });
+}
+
+/// Minimal sum of products of the function with on-set `minterms` over `variables`.
+pub fn minimal_sop(
+    variables: &[Symbol],
+    minterms: &[u64],
+    dont_cares: &[u64],
+) -> Result<AstNode, Error> {
+    let cover = minimal_cover(variables.len(), minterms, dont_cares)?;
+    let products = cover
+        .iter()
+        .map(|term| term.to_product(variables))
+        .collect();
+    Ok(AstNode::right_fold(products, Operator::Or, false))
+}
+
+/// Minimal product of sums of the function with on-set `minterms` over `variables`,
+/// obtained as the complement of a minimal sum of products of its off-set, which is
+/// enumerated for at most [`MAX_VARIABLES`] variables.
+pub fn minimal_pos(
+    variables: &[Symbol],
+    minterms: &[u64],
+    dont_cares: &[u64],
+) -> Result<AstNode, Error> {
+    check_size(variables.len())?;
+    check_terms(variables.len(), minterms.iter().chain(dont_cares))?;
+    let on: HashSet<u64> = minterms.iter().chain(dont_cares).copied().collect();
+    let off: Vec<u64> = (0..1u64 << variables.len())
+        .filter(|m| !on.contains(m))
+        .collect();
+
+    let cover = minimal_cover(variables.len(), &off, dont_cares)?;
+    let clauses = cover.iter().map(|term| term.to_clause(variables)).collect();
+    Ok(AstNode::right_fold(clauses, Operator::And, true))
+}
+
+// Every row of a truth table is enumerated, so the number of variables is bounded
+fn check_size(variables: usize) -> Result<(), Error> {
+    if variables > MAX_VARIABLES {
+        return Err(Error::TooManyVariables {
+            variables,
+            limit: MAX_VARIABLES,
+        });
+    }
+    Ok(())
+}
+
+// A term holds at most 64 variables, and a minterm of `num_vars` variables is below
+// `2^num_vars`
+fn check_terms<'a>(num_vars: usize, terms: impl Iterator<Item = &'a u64>) -> Result<(), Error> {
+    if num_vars > 64 {
+        return Err(Error::TooManyVariables {
+            variables: num_vars,
+            limit: 64,
+        });
+    }
+    for &minterm in terms {
+        if num_vars < 64 && minterm >> num_vars != 0 {
+            return Err(Error::MintermRange {
+                minterm,
+                variables: num_vars,
+            });
+        }
+    }
+    Ok(())
+}
+
+impl AstNode {
+    /// Sorted variables of the formula and the minterms on which it is true, for at most
+    /// [`MAX_VARIABLES`] variables.
+    pub fn minterms(&self) -> Result<(Vec<Symbol>, Vec<u64>), Error> {
+        let mut variables: Vec<Symbol> = self.get_variables().into_iter().collect();
+        variables.sort();
+
+        let n = variables.len();
+        check_size(n)?;
+        let minterms = (0..1u64 << n)
+            .filter(|m| {
+                let values: HashMap<Symbol, bool> = variables
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &name)| (name, m >> (n - 1 - i) & 1 == 1))
+                    .collect();
+                self.evaluate(&values)
+                    .expect("Every variable is assigned a value")
+            })
+            .collect();
+
+        Ok((variables, minterms))
+    }
+
+    pub fn minimal_sop(&self) -> Result<AstNode, Error> {
+        let (variables, minterms) = self.minterms()?;
+        minimal_sop(&variables, &minterms, &[])
+    }
+
+    pub fn minimal_pos(&self) -> Result<AstNode, Error> {
+        let (variables, minterms) = self.minterms()?;
+        minimal_pos(&variables, &minterms, &[])
+    }
+}
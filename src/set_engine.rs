@@ -0,0 +1,246 @@
+use crate::ast::{AstNode, Operator};
+use crate::error::Error;
+use crate::symbol::Symbol;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Dense set of indices below a fixed capacity, stored as packed words. Combining two
+/// sets of different capacities is an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bitset {
+    words: Vec<u64>,
+    capacity: usize,
+}
+
+impl Bitset {
+    /// Empty set of indices below `capacity`.
+    pub fn new(capacity: usize) -> Bitset {
+        Bitset {
+            words: vec![0; capacity.div_ceil(64)],
+            capacity,
+        }
+    }
+
+    /// Set of every index below `capacity`.
+    pub fn full(capacity: usize) -> Bitset {
+        let mut bitset = Bitset {
+            words: vec![!0; capacity.div_ceil(64)],
+            capacity,
+        };
+        // Clear the bits past the capacity in a partial last word
+        let rest = capacity % 64;
+        if rest != 0 {
+            *bitset.words.last_mut().unwrap() = (1 << rest) - 1;
+        }
+        bitset
+    }
+
+    /// Bound on the indices of the set, not the number of indices in it.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    pub fn insert(&mut self, index: usize) {
+        assert!(index < self.capacity, "Index {} is out of range", index);
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.capacity && self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    /// Number of indices in the set.
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Indices in the set, in increasing order.
+    pub fn ones(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+
+    // Combine word by word with a set of the same capacity
+    fn combine(&mut self, other: &Bitset, op: impl Fn(u64, u64) -> u64) -> Result<(), Error> {
+        if self.capacity != other.capacity {
+            return Err(Error::CapacityMismatch {
+                capacity: self.capacity,
+                other: other.capacity,
+            });
+        }
+        for (w, o) in self.words.iter_mut().zip(&other.words) {
+            *w = op(*w, *o);
+        }
+        Ok(())
+    }
+
+    pub fn intersect_with(&mut self, other: &Bitset) -> Result<(), Error> {
+        self.combine(other, |a, b| a & b)
+    }
+
+    pub fn union_with(&mut self, other: &Bitset) -> Result<(), Error> {
+        self.combine(other, |a, b| a | b)
+    }
+
+    pub fn symmetric_difference_with(&mut self, other: &Bitset) -> Result<(), Error> {
+        self.combine(other, |a, b| a ^ b)
+    }
+
+    /// Remove the indices of `other`, a word-wise AND NOT.
+    pub fn difference_with(&mut self, other: &Bitset) -> Result<(), Error> {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    // Replace the set by its complement in `universe`
+    fn complement_in(&mut self, universe: &Bitset) -> Result<(), Error> {
+        self.combine(universe, |a, u| u & !a)
+    }
+}
+
+/// Evaluates set formulas on [`Bitset`]s: every element is mapped to an index once, so
+/// each operator costs one pass over the words of the sets whatever the element type.
+#[derive(Debug, Clone)]
+pub struct SetEngine<T> {
+    elements: Vec<T>,
+    indices: HashMap<T, usize>,
+    // Indices of the universe, the first ones, which may not cover every indexed element
+    universe: Bitset,
+    universe_size: usize,
+}
+
+impl<T: Eq + Hash + Clone> SetEngine<T> {
+    pub fn new(universe: impl IntoIterator<Item = T>) -> SetEngine<T> {
+        SetEngine::with_extra(universe, [])
+    }
+
+    // Also index the `extra` elements, which stay outside the universe
+    pub(crate) fn with_extra(
+        universe: impl IntoIterator<Item = T>,
+        extra: impl IntoIterator<Item = T>,
+    ) -> SetEngine<T> {
+        let mut engine = SetEngine {
+            elements: Vec::new(),
+            indices: HashMap::new(),
+            universe: Bitset::new(0),
+            universe_size: 0,
+        };
+        for element in universe {
+            engine.add(element);
+        }
+        engine.universe_size = engine.elements.len();
+        for element in extra {
+            engine.add(element);
+        }
+
+        let len = engine.elements.len();
+        engine.universe = Bitset::full(engine.universe_size);
+        engine.universe.words.resize(len.div_ceil(64), 0);
+        engine.universe.capacity = len;
+        engine
+    }
+
+    fn add(&mut self, element: T) {
+        if !self.indices.contains_key(&element) {
+            self.indices.insert(element.clone(), self.elements.len());
+            self.elements.push(element);
+        }
+    }
+
+    /// The elements of the universe, in index order.
+    pub fn universe(&self) -> &[T] {
+        &self.elements[..self.universe_size]
+    }
+
+    pub fn index(&self, element: &T) -> Option<usize> {
+        self.indices.get(element).copied()
+    }
+
+    /// Bitset of the elements of `set`, `None` when one of them is not indexed.
+    pub fn bitset<'a>(&self, set: impl IntoIterator<Item = &'a T>) -> Option<Bitset>
+    where
+        T: 'a,
+    {
+        let mut bitset = Bitset::new(self.elements.len());
+        for element in set {
+            bitset.insert(self.index(element)?);
+        }
+        Some(bitset)
+    }
+
+    /// Elements of a bitset of this engine, in index order.
+    pub fn elements<'a>(&'a self, bitset: &'a Bitset) -> impl Iterator<Item = &'a T> + 'a {
+        bitset.ones().map(|index| &self.elements[index])
+    }
+
+    /// Evaluate the formula on the bitsets bound to its variables, complements being
+    /// taken in the universe. Every bound bitset must come from this engine, or have as
+    /// many indices as it has elements.
+    pub fn evaluate(
+        &self,
+        node: &AstNode,
+        sets: &HashMap<Symbol, Bitset>,
+    ) -> Result<Bitset, Error> {
+        let mut variables: Vec<Symbol> = node.get_variables().into_iter().collect();
+        variables.sort();
+        for var in variables {
+            let set = sets.get(&var).ok_or(Error::UnboundVariable(var))?;
+            if set.capacity != self.elements.len() {
+                return Err(Error::BitsetCapacity {
+                    variable: var,
+                    capacity: set.capacity,
+                    expected: self.elements.len(),
+                });
+            }
+        }
+        self.evaluate_checked(node, sets)
+    }
+
+    // Evaluate once every variable is known to be bound to a bitset of the right capacity
+    fn evaluate_checked(
+        &self,
+        node: &AstNode,
+        sets: &HashMap<Symbol, Bitset>,
+    ) -> Result<Bitset, Error> {
+        match node {
+            AstNode::Constant(false) => Ok(Bitset::new(self.elements.len())),
+            AstNode::Constant(true) => Ok(self.universe.clone()),
+            AstNode::Variable(var) => sets.get(var).cloned().ok_or(Error::UnboundVariable(*var)),
+            AstNode::Not(child) => {
+                let mut set = self.evaluate_checked(child, sets)?;
+                set.complement_in(&self.universe)?;
+                Ok(set)
+            }
+            AstNode::BinaryOperator(op, left, right) => {
+                let mut set = self.evaluate_checked(left, sets)?;
+                let right = self.evaluate_checked(right, sets)?;
+                match op {
+                    Operator::And => set.intersect_with(&right)?,
+                    Operator::Or => set.union_with(&right)?,
+                    Operator::Xor => set.symmetric_difference_with(&right)?,
+                    Operator::Implies => {
+                        set.complement_in(&self.universe)?;
+                        set.union_with(&right)?;
+                    }
+                    Operator::Iff => {
+                        set.symmetric_difference_with(&right)?;
+                        set.complement_in(&self.universe)?;
+                    }
+                }
+                Ok(set)
+            }
+        }
+    }
+}
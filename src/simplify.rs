@@ -0,0 +1,318 @@
+use crate::ast::{AstNode, Operator};
+use std::fmt;
+
+/// Rewrite rules applied by [`AstNode::simplify_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rule {
+    // x & 1 = x, x & 0 = 0, x ^ 1 = !x, 0 > x = 1, !1 = 0, ...
+    Constant,
+    // !!x = x
+    DoubleNegation,
+    // x & x = x, x | x = x, x ^ x = 0, x = x = 1, x > x = 1
+    Idempotence,
+    // x & !x = 0, x | !x = 1, x ^ !x = 1, x = !x = 0, x > !x = !x
+    Complementation,
+    // x & (x | y) = x, x | (x & y) = x
+    Absorption,
+    // (x | y) & (x | y | z) = x | y, (x & y) | (x & y & z) = x & y
+    Subsumption,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 6] = [
+        Rule::Constant,
+        Rule::DoubleNegation,
+        Rule::Idempotence,
+        Rule::Complementation,
+        Rule::Absorption,
+        Rule::Subsumption,
+    ];
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Rule::Constant => "constant",
+            Rule::DoubleNegation => "double negation",
+            Rule::Idempotence => "idempotence",
+            Rule::Complementation => "complementation",
+            Rule::Absorption => "absorption",
+            Rule::Subsumption => "subsumption",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A rewrite of a subformula by one rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub rule: Rule,
+    pub before: AstNode,
+    pub after: AstNode,
+}
+
+/// Result of [`AstNode::simplify_with`] with the rewrites performed, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simplification {
+    pub result: AstNode,
+    pub trace: Vec<Step>,
+}
+
+struct Simplifier<'a> {
+    rules: &'a [Rule],
+    trace: Vec<Step>,
+}
+
+fn not(node: AstNode) -> AstNode {
+    AstNode::Not(Box::new(node))
+}
+
+// Whether `a` is the negation of `b` or the other way around
+fn complementary(a: &AstNode, b: &AstNode) -> bool {
+    matches!(a, AstNode::Not(inner) if **inner == *b)
+        || matches!(b, AstNode::Not(inner) if **inner == *a)
+}
+
+impl Simplifier<'_> {
+    fn enabled(&self, rule: Rule) -> bool {
+        self.rules.contains(&rule)
+    }
+
+    fn record(&mut self, rule: Rule, before: &AstNode, after: &AstNode) {
+        self.trace.push(Step {
+            rule,
+            before: before.clone(),
+            after: after.clone(),
+        });
+    }
+
+    // Simplify the children, then the node itself
+    fn pass(&mut self, node: &AstNode) -> AstNode {
+        match node {
+            AstNode::Constant(_) | AstNode::Variable(_) => node.clone(),
+            AstNode::Not(child) => {
+                let node = not(self.pass(child));
+                self.negation(node)
+            }
+            AstNode::BinaryOperator(op, left, right) => {
+                let node = AstNode::BinaryOperator(
+                    op.clone(),
+                    Box::new(self.pass(left)),
+                    Box::new(self.pass(right)),
+                );
+                match op {
+                    Operator::And | Operator::Or => self.associative(node, op),
+                    _ => self.binary(node),
+                }
+            }
+        }
+    }
+
+    fn negation(&mut self, node: AstNode) -> AstNode {
+        let AstNode::Not(child) = &node else {
+            return node;
+        };
+        let (rule, after) = match &**child {
+            AstNode::Constant(value) => (Rule::Constant, AstNode::Constant(!value)),
+            AstNode::Not(inner) => (Rule::DoubleNegation, (**inner).clone()),
+            _ => return node,
+        };
+        if !self.enabled(rule) {
+            return node;
+        }
+        self.record(rule, &node, &after);
+        after
+    }
+
+    // XOR, equivalence and implication
+    fn binary(&mut self, node: AstNode) -> AstNode {
+        let AstNode::BinaryOperator(op, left, right) = &node else {
+            return node;
+        };
+        let (left, right) = (&**left, &**right);
+
+        let rewrite = match (op, left, right) {
+            (Operator::Xor, AstNode::Constant(false), x)
+            | (Operator::Xor, x, AstNode::Constant(false)) => Some((Rule::Constant, x.clone())),
+            (Operator::Xor, AstNode::Constant(true), x)
+            | (Operator::Xor, x, AstNode::Constant(true)) => Some((Rule::Constant, not(x.clone()))),
+            (Operator::Iff, AstNode::Constant(true), x)
+            | (Operator::Iff, x, AstNode::Constant(true)) => Some((Rule::Constant, x.clone())),
+            (Operator::Iff, AstNode::Constant(false), x)
+            | (Operator::Iff, x, AstNode::Constant(false)) => {
+                Some((Rule::Constant, not(x.clone())))
+            }
+            (Operator::Implies, AstNode::Constant(false), _)
+            | (Operator::Implies, _, AstNode::Constant(true)) => {
+                Some((Rule::Constant, AstNode::Constant(true)))
+            }
+            (Operator::Implies, AstNode::Constant(true), x) => Some((Rule::Constant, x.clone())),
+            (Operator::Implies, x, AstNode::Constant(false)) => {
+                Some((Rule::Constant, not(x.clone())))
+            }
+            (_, x, y) if x == y => {
+                let value = *op != Operator::Xor;
+                Some((Rule::Idempotence, AstNode::Constant(value)))
+            }
+            (Operator::Implies, x, y) if complementary(x, y) => {
+                Some((Rule::Complementation, y.clone()))
+            }
+            (_, x, y) if complementary(x, y) => {
+                let value = *op == Operator::Xor;
+                Some((Rule::Complementation, AstNode::Constant(value)))
+            }
+            _ => None,
+        };
+
+        match rewrite {
+            Some((rule, after)) if self.enabled(rule) => {
+                self.record(rule, &node, &after);
+                after
+            }
+            _ => node,
+        }
+    }
+
+    // AND and OR, whose nested operands are rewritten as one list
+    fn associative(&mut self, node: AstNode, op: &Operator) -> AstNode {
+        let dual = match op {
+            Operator::And => Operator::Or,
+            _ => Operator::And,
+        };
+        // Value that decides the result, false for AND
+        let absorbing = *op == Operator::Or;
+        let mut operands = node.collect_operands(op);
+        // Members of every operand of the dual operator
+        let dual_members = |operands: &[AstNode]| -> Vec<Option<Vec<AstNode>>> {
+            operands
+                .iter()
+                .map(|o| match o {
+                    AstNode::BinaryOperator(o_op, _, _) if *o_op == dual => {
+                        Some(o.collect_operands(&dual))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+        // Node after the rules applied so far
+        let mut current = node;
+
+        let mut apply = |simplifier: &mut Simplifier,
+                         rule: Rule,
+                         operands: &mut Vec<AstNode>,
+                         after: Vec<AstNode>| {
+            if !simplifier.enabled(rule) || after == *operands {
+                return;
+            }
+            let result = AstNode::right_fold(after.clone(), op.clone(), !absorbing);
+            simplifier.record(rule, &current, &result);
+            *operands = after;
+            current = result;
+        };
+
+        // Constant propagation
+        if operands.contains(&AstNode::Constant(absorbing)) {
+            apply(
+                self,
+                Rule::Constant,
+                &mut operands,
+                vec![AstNode::Constant(absorbing)],
+            );
+        } else {
+            let kept = operands
+                .iter()
+                .filter(|o| **o != AstNode::Constant(!absorbing))
+                .cloned()
+                .collect();
+            apply(self, Rule::Constant, &mut operands, kept);
+        }
+
+        let mut kept: Vec<AstNode> = Vec::new();
+        for operand in &operands {
+            if !kept.contains(operand) {
+                kept.push(operand.clone());
+            }
+        }
+        apply(self, Rule::Idempotence, &mut operands, kept);
+
+        if operands
+            .iter()
+            .any(|a| operands.iter().any(|b| complementary(a, b)))
+        {
+            apply(
+                self,
+                Rule::Complementation,
+                &mut operands,
+                vec![AstNode::Constant(absorbing)],
+            );
+        }
+
+        let members = dual_members(&operands);
+
+        let kept = operands
+            .iter()
+            .zip(&members)
+            .filter(|(_, members)| match members {
+                Some(members) => !operands.iter().any(|other| members.contains(other)),
+                None => true,
+            })
+            .map(|(o, _)| o.clone())
+            .collect();
+        apply(self, Rule::Absorption, &mut operands, kept);
+
+        let members = dual_members(&operands);
+        let subsumed = |i: usize| {
+            let Some(larger) = &members[i] else {
+                return false;
+            };
+            members
+                .iter()
+                .enumerate()
+                .any(|(j, smaller)| match smaller {
+                    // Of two equal lists, only the later one goes
+                    Some(smaller) if j != i => {
+                        smaller.iter().all(|m| larger.contains(m))
+                            && (smaller.len() < larger.len() || j < i)
+                    }
+                    _ => false,
+                })
+        };
+        let kept = (0..operands.len())
+            .filter(|&i| !subsumed(i))
+            .map(|i| operands[i].clone())
+            .collect();
+        apply(self, Rule::Subsumption, &mut operands, kept);
+
+        current
+    }
+}
+
+impl AstNode {
+    /// The formula rewritten with every [`Rule`] until none applies.
+    pub fn simplify(&self) -> AstNode {
+        self.simplify_with(&Rule::ALL).result
+    }
+
+    /// The formula rewritten bottom-up with `rules` until none applies, with the trace of
+    /// the rewrites. The result is equivalent to the formula.
+    pub fn simplify_with(&self, rules: &[Rule]) -> Simplification {
+        let mut simplifier = Simplifier {
+            rules,
+            trace: Vec::new(),
+        };
+
+        let mut result = self.clone();
+        loop {
+            let steps = simplifier.trace.len();
+            result = simplifier.pass(&result);
+            if simplifier.trace.len() == steps {
+                break;
+            }
+        }
+
+        Simplification {
+            result,
+            trace: simplifier.trace,
+        }
+    }
+}
@@ -0,0 +1,616 @@
+use crate::ast::AstNode;
+use crate::cnf::{Cnf, Lit};
+use crate::symbol::Symbol;
+use crate::tseitin::Mode;
+use std::collections::HashMap;
+
+// Index of a clause in the clause database
+type ClauseRef = usize;
+
+const VAR_DECAY: f64 = 0.95;
+const CLAUSE_DECAY: f64 = 0.999;
+const RESTART_BASE: u64 = 100;
+
+struct Clause {
+    lits: Vec<Lit>,
+    learnt: bool,
+    activity: f64,
+    deleted: bool,
+}
+
+// Entry of a watch list, `blocker` is another literal of the clause: when it is
+// already true the clause is satisfied and does not need to be visited
+#[derive(Clone, Copy)]
+struct Watcher {
+    cref: ClauseRef,
+    blocker: Lit,
+}
+
+// Binary max-heap of variables ordered by activity, used to pick decision variables
+#[derive(Default)]
+struct VarOrder {
+    heap: Vec<u32>,
+    // Position of each variable in `heap`, if present
+    indices: Vec<Option<usize>>,
+}
+
+impl VarOrder {
+    fn contains(&self, var: u32) -> bool {
+        self.indices[var as usize].is_some()
+    }
+
+    fn insert(&mut self, var: u32, activity: &[f64]) {
+        if self.contains(var) {
+            return;
+        }
+        self.indices[var as usize] = Some(self.heap.len());
+        self.heap.push(var);
+        self.sift_up(self.heap.len() - 1, activity);
+    }
+
+    fn pop(&mut self, activity: &[f64]) -> Option<u32> {
+        let top = *self.heap.first()?;
+        let last = self.heap.pop().unwrap();
+        self.indices[top as usize] = None;
+        if !self.heap.is_empty() {
+            self.heap[0] = last;
+            self.indices[last as usize] = Some(0);
+            self.sift_down(0, activity);
+        }
+        Some(top)
+    }
+
+    // Restore the heap property after the activity of `var` increased
+    fn increased(&mut self, var: u32, activity: &[f64]) {
+        if let Some(pos) = self.indices[var as usize] {
+            self.sift_up(pos, activity);
+        }
+    }
+
+    fn sift_up(&mut self, mut pos: usize, activity: &[f64]) {
+        let var = self.heap[pos];
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if activity[self.heap[parent] as usize] >= activity[var as usize] {
+                break;
+            }
+            self.heap[pos] = self.heap[parent];
+            self.indices[self.heap[pos] as usize] = Some(pos);
+            pos = parent;
+        }
+        self.heap[pos] = var;
+        self.indices[var as usize] = Some(pos);
+    }
+
+    fn sift_down(&mut self, mut pos: usize, activity: &[f64]) {
+        let var = self.heap[pos];
+        loop {
+            let left = 2 * pos + 1;
+            if left >= self.heap.len() {
+                break;
+            }
+            let right = left + 1;
+            let child = if right < self.heap.len()
+                && activity[self.heap[right] as usize] > activity[self.heap[left] as usize]
+            {
+                right
+            } else {
+                left
+            };
+            if activity[self.heap[child] as usize] <= activity[var as usize] {
+                break;
+            }
+            self.heap[pos] = self.heap[child];
+            self.indices[self.heap[pos] as usize] = Some(pos);
+            pos = child;
+        }
+        self.heap[pos] = var;
+        self.indices[var as usize] = Some(pos);
+    }
+}
+
+/// Conflict-driven clause learning SAT solver.
+///
+/// Propagation uses two watched literals per clause. Conflicts are analysed down to the
+/// first unique implication point, the learnt clause is minimized and the search jumps
+/// back to the second highest level of that clause. Decisions follow VSIDS activities
+/// with phase saving, restarts follow the Luby sequence and the learnt clause database
+/// is periodically halved by activity.
+#[derive(Default)]
+pub struct Solver {
+    clauses: Vec<Clause>,
+    // Clauses watching each literal, indexed by `Lit::index`
+    watches: Vec<Vec<Watcher>>,
+    assigns: Vec<Option<bool>>,
+    level: Vec<usize>,
+    reason: Vec<Option<ClauseRef>>,
+    trail: Vec<Lit>,
+    // Start of each decision level in the trail
+    trail_lim: Vec<usize>,
+    // Next trail position to propagate
+    qhead: usize,
+    activity: Vec<f64>,
+    var_inc: f64,
+    clause_inc: f64,
+    order: VarOrder,
+    // Last value of each variable, reused when it is picked as a decision again
+    polarity: Vec<bool>,
+    seen: Vec<bool>,
+    num_learnts: usize,
+    max_learnts: f64,
+    model: Vec<bool>,
+    // False once the clauses are known to be unsatisfiable
+    ok: bool,
+}
+
+impl Solver {
+    pub fn new() -> Solver {
+        Solver {
+            var_inc: 1.0,
+            clause_inc: 1.0,
+            ok: true,
+            ..Solver::default()
+        }
+    }
+
+    pub fn from_cnf(cnf: &Cnf) -> Solver {
+        let mut solver = Solver::new();
+        while solver.num_vars() < cnf.num_vars() {
+            solver.new_var();
+        }
+        for clause in cnf.clauses() {
+            solver.add_clause(clause);
+        }
+        solver
+    }
+
+    pub fn num_vars(&self) -> u32 {
+        self.assigns.len() as u32
+    }
+
+    pub fn new_var(&mut self) -> u32 {
+        let var = self.num_vars();
+        self.watches.push(Vec::new());
+        self.watches.push(Vec::new());
+        self.assigns.push(None);
+        self.level.push(0);
+        self.reason.push(None);
+        self.activity.push(0.0);
+        self.polarity.push(false);
+        self.seen.push(false);
+        self.order.indices.push(None);
+        self.order.insert(var, &self.activity);
+        var
+    }
+
+    fn value(&self, lit: Lit) -> Option<bool> {
+        self.assigns[lit.var() as usize].map(|value| value != lit.is_negated())
+    }
+
+    fn decision_level(&self) -> usize {
+        self.trail_lim.len()
+    }
+
+    /// Add a clause, returns false if the clauses are now known to be unsatisfiable.
+    pub fn add_clause(&mut self, lits: &[Lit]) -> bool {
+        if !self.ok {
+            return false;
+        }
+        if let Some(max) = lits.iter().map(|lit| lit.var()).max() {
+            while self.num_vars() <= max {
+                self.new_var();
+            }
+        }
+
+        // Clauses are added at the root level: drop false and duplicate literals,
+        // skip satisfied and tautological clauses
+        let mut clause: Vec<Lit> = lits.to_vec();
+        clause.sort();
+        clause.dedup();
+        if clause.windows(2).any(|pair| pair[0] == !pair[1]) {
+            return true;
+        }
+        if clause.iter().any(|&lit| self.value(lit) == Some(true)) {
+            return true;
+        }
+        clause.retain(|&lit| self.value(lit).is_none());
+
+        match clause.len() {
+            0 => self.ok = false,
+            1 => {
+                self.enqueue(clause[0], None);
+                self.ok = self.propagate().is_none();
+            }
+            _ => {
+                self.attach(clause, false);
+            }
+        }
+        self.ok
+    }
+
+    fn attach(&mut self, lits: Vec<Lit>, learnt: bool) -> ClauseRef {
+        let cref = self.clauses.len();
+        self.watches[lits[0].index()].push(Watcher {
+            cref,
+            blocker: lits[1],
+        });
+        self.watches[lits[1].index()].push(Watcher {
+            cref,
+            blocker: lits[0],
+        });
+        self.clauses.push(Clause {
+            lits,
+            learnt,
+            activity: 0.0,
+            deleted: false,
+        });
+        if learnt {
+            self.num_learnts += 1;
+        }
+        cref
+    }
+
+    fn enqueue(&mut self, lit: Lit, reason: Option<ClauseRef>) {
+        let var = lit.var() as usize;
+        self.assigns[var] = Some(!lit.is_negated());
+        self.level[var] = self.decision_level();
+        self.reason[var] = reason;
+        self.trail.push(lit);
+    }
+
+    // Propagate every enqueued literal, returning the conflicting clause if any
+    fn propagate(&mut self) -> Option<ClauseRef> {
+        while self.qhead < self.trail.len() {
+            let false_lit = !self.trail[self.qhead];
+            self.qhead += 1;
+
+            let mut watchers = std::mem::take(&mut self.watches[false_lit.index()]);
+            let mut conflict = None;
+            let mut i = 0;
+            let mut j = 0;
+
+            while i < watchers.len() {
+                let watcher = watchers[i];
+                i += 1;
+                if self.value(watcher.blocker) == Some(true) {
+                    watchers[j] = watcher;
+                    j += 1;
+                    continue;
+                }
+
+                // Make sure the false literal is the second watch
+                let lits = &mut self.clauses[watcher.cref].lits;
+                if lits[0] == false_lit {
+                    lits.swap(0, 1);
+                }
+                let first = lits[0];
+                let kept = Watcher {
+                    cref: watcher.cref,
+                    blocker: first,
+                };
+                if first != watcher.blocker && self.value(first) == Some(true) {
+                    watchers[j] = kept;
+                    j += 1;
+                    continue;
+                }
+
+                // Look for a new literal to watch
+                let lits = &self.clauses[watcher.cref].lits;
+                if let Some(k) = (2..lits.len()).find(|&k| self.value(lits[k]) != Some(false)) {
+                    let lits = &mut self.clauses[watcher.cref].lits;
+                    lits.swap(1, k);
+                    let watched = lits[1];
+                    self.watches[watched.index()].push(kept);
+                    continue;
+                }
+
+                // The clause is unit or conflicting
+                watchers[j] = kept;
+                j += 1;
+                if self.value(first) == Some(false) {
+                    conflict = Some(watcher.cref);
+                    while i < watchers.len() {
+                        watchers[j] = watchers[i];
+                        i += 1;
+                        j += 1;
+                    }
+                } else {
+                    self.enqueue(first, Some(watcher.cref));
+                }
+            }
+
+            watchers.truncate(j);
+            self.watches[false_lit.index()] = watchers;
+            if conflict.is_some() {
+                self.qhead = self.trail.len();
+                return conflict;
+            }
+        }
+
+        None
+    }
+
+    // First UIP conflict analysis, returns the learnt clause with the asserting literal
+    // first and the literal of the backjump level second, along with that level
+    fn analyze(&mut self, conflict: ClauseRef) -> (Vec<Lit>, usize) {
+        let mut learnt = vec![Lit::positive(0)];
+        let mut pending = 0;
+        let mut index = self.trail.len();
+        let mut implied: Option<Lit> = None;
+        let mut cref = conflict;
+
+        loop {
+            self.bump_clause(cref);
+
+            // Reason clauses start with the literal they implied
+            let start = if implied.is_some() { 1 } else { 0 };
+            for k in start..self.clauses[cref].lits.len() {
+                let lit = self.clauses[cref].lits[k];
+                let var = lit.var() as usize;
+                if !self.seen[var] && self.level[var] > 0 {
+                    self.bump_var(lit.var());
+                    self.seen[var] = true;
+                    if self.level[var] >= self.decision_level() {
+                        pending += 1;
+                    } else {
+                        learnt.push(lit);
+                    }
+                }
+            }
+
+            // Walk back the trail to the next literal involved in the conflict
+            loop {
+                index -= 1;
+                if self.seen[self.trail[index].var() as usize] {
+                    break;
+                }
+            }
+            let lit = self.trail[index];
+            self.seen[lit.var() as usize] = false;
+            pending -= 1;
+            if pending == 0 {
+                implied = Some(lit);
+                break;
+            }
+            implied = Some(lit);
+            cref = self.reason[lit.var() as usize].expect("Implied literal has a reason");
+        }
+        learnt[0] = !implied.unwrap();
+
+        // Drop literals implied by the other literals of the clause
+        let marked = learnt.clone();
+        learnt = std::iter::once(learnt[0])
+            .chain(marked[1..].iter().copied().filter(|&lit| {
+                match self.reason[lit.var() as usize] {
+                    None => true,
+                    Some(reason) => self.clauses[reason].lits[1..].iter().any(|other| {
+                        let var = other.var() as usize;
+                        !self.seen[var] && self.level[var] > 0
+                    }),
+                }
+            }))
+            .collect();
+        for lit in &marked {
+            self.seen[lit.var() as usize] = false;
+        }
+
+        // Put the literal with the highest level after the asserting one
+        let mut backjump = 0;
+        if learnt.len() > 1 {
+            let max = (1..learnt.len())
+                .max_by_key(|&k| self.level[learnt[k].var() as usize])
+                .unwrap();
+            learnt.swap(1, max);
+            backjump = self.level[learnt[1].var() as usize];
+        }
+
+        (learnt, backjump)
+    }
+
+    fn cancel_until(&mut self, level: usize) {
+        if self.decision_level() <= level {
+            return;
+        }
+        let start = self.trail_lim[level];
+        for k in (start..self.trail.len()).rev() {
+            let lit = self.trail[k];
+            let var = lit.var() as usize;
+            self.assigns[var] = None;
+            self.reason[var] = None;
+            self.polarity[var] = !lit.is_negated();
+            self.order.insert(lit.var(), &self.activity);
+        }
+        self.trail.truncate(start);
+        self.trail_lim.truncate(level);
+        self.qhead = start;
+    }
+
+    fn bump_var(&mut self, var: u32) {
+        self.activity[var as usize] += self.var_inc;
+        if self.activity[var as usize] > 1e100 {
+            for activity in self.activity.iter_mut() {
+                *activity *= 1e-100;
+            }
+            self.var_inc *= 1e-100;
+        }
+        self.order.increased(var, &self.activity);
+    }
+
+    fn bump_clause(&mut self, cref: ClauseRef) {
+        if !self.clauses[cref].learnt {
+            return;
+        }
+        self.clauses[cref].activity += self.clause_inc;
+        if self.clauses[cref].activity > 1e20 {
+            for clause in self.clauses.iter_mut().filter(|clause| clause.learnt) {
+                clause.activity *= 1e-20;
+            }
+            self.clause_inc *= 1e-20;
+        }
+    }
+
+    fn pick_branch(&mut self) -> Option<Lit> {
+        while let Some(var) = self.order.pop(&self.activity) {
+            if self.assigns[var as usize].is_none() {
+                return Some(Lit::new(var, !self.polarity[var as usize]));
+            }
+        }
+        None
+    }
+
+    // A clause is locked while it is the reason of a current assignment
+    fn is_locked(&self, cref: ClauseRef) -> bool {
+        let first = self.clauses[cref].lits[0];
+        self.reason[first.var() as usize] == Some(cref) && self.value(first) == Some(true)
+    }
+
+    // Delete the least active half of the learnt clauses
+    fn reduce_db(&mut self) {
+        let mut learnts: Vec<ClauseRef> = (0..self.clauses.len())
+            .filter(|&cref| self.clauses[cref].learnt && !self.clauses[cref].deleted)
+            .collect();
+        learnts.sort_by(|&a, &b| {
+            self.clauses[a]
+                .activity
+                .total_cmp(&self.clauses[b].activity)
+        });
+
+        for &cref in &learnts[..learnts.len() / 2] {
+            if self.clauses[cref].lits.len() > 2 && !self.is_locked(cref) {
+                self.clauses[cref].deleted = true;
+                self.clauses[cref].lits = Vec::new();
+                self.num_learnts -= 1;
+            }
+        }
+
+        let clauses = &self.clauses;
+        for watchers in self.watches.iter_mut() {
+            watchers.retain(|watcher| !clauses[watcher.cref].deleted);
+        }
+    }
+
+    // Search until a model is found, unsatisfiability is proved (`Some`) or
+    // `max_conflicts` is reached (`None`)
+    fn search(&mut self, max_conflicts: u64) -> Option<bool> {
+        let mut conflicts = 0;
+        loop {
+            if let Some(conflict) = self.propagate() {
+                conflicts += 1;
+                if self.decision_level() == 0 {
+                    self.ok = false;
+                    return Some(false);
+                }
+
+                let (learnt, backjump) = self.analyze(conflict);
+                self.cancel_until(backjump);
+                if learnt.len() == 1 {
+                    self.enqueue(learnt[0], None);
+                } else {
+                    let asserting = learnt[0];
+                    let cref = self.attach(learnt, true);
+                    self.bump_clause(cref);
+                    self.enqueue(asserting, Some(cref));
+                }
+
+                self.var_inc /= VAR_DECAY;
+                self.clause_inc /= CLAUSE_DECAY;
+            } else {
+                if conflicts >= max_conflicts {
+                    self.cancel_until(0);
+                    return None;
+                }
+                if self.num_learnts as f64 >= self.max_learnts + self.trail.len() as f64 {
+                    self.reduce_db();
+                    self.max_learnts *= 1.1;
+                }
+
+                match self.pick_branch() {
+                    Some(lit) => {
+                        self.trail_lim.push(self.trail.len());
+                        self.enqueue(lit, None);
+                    }
+                    None => {
+                        self.model = self.assigns.iter().map(|value| value.unwrap()).collect();
+                        return Some(true);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decide whether the clauses are satisfiable, a model is then available through `model`.
+    pub fn solve(&mut self) -> bool {
+        if !self.ok {
+            return false;
+        }
+
+        self.max_learnts = (self.clauses.len() as f64 / 3.0).max(1000.0);
+        let mut restarts = 0;
+        loop {
+            let max_conflicts = luby(restarts) * RESTART_BASE;
+            if let Some(result) = self.search(max_conflicts) {
+                self.cancel_until(0);
+                return result;
+            }
+            restarts += 1;
+        }
+    }
+
+    /// Value of every variable in the last model found by `solve`.
+    pub fn model(&self) -> &[bool] {
+        &self.model
+    }
+}
+
+// Luby restart sequence: 1, 1, 2, 1, 1, 2, 4, 1, 1, 2, 1, 1, 2, 4, 8, ...
+fn luby(mut index: u64) -> u64 {
+    let mut size = 1;
+    let mut power = 0;
+    while size < index + 1 {
+        power += 1;
+        size = 2 * size + 1;
+    }
+    while size - 1 != index {
+        size = (size - 1) >> 1;
+        power -= 1;
+        index %= size;
+    }
+    1 << power
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Solution {
+    // A value for every variable of the formula that makes it true
+    Sat(HashMap<Symbol, bool>),
+    Unsat,
+}
+
+impl AstNode {
+    /// Find an assignment satisfying the formula with the CDCL solver, run on the
+    /// Plaisted-Greenbaum encoding of the formula.
+    pub fn solve(&self) -> Solution {
+        let encoding = self.to_definitional_cnf(Mode::PlaistedGreenbaum);
+        let mut solver = Solver::from_cnf(&encoding.cnf);
+        if !solver.solve() {
+            return Solution::Unsat;
+        }
+
+        let model: HashMap<Symbol, bool> = encoding
+            .inputs
+            .iter()
+            .map(|(&name, &var)| (name, solver.model()[var as usize]))
+            .collect();
+        // Checked with a compiled program, as `evaluate` recurses once per level
+        if cfg!(debug_assertions) {
+            let compiled = self.compile();
+            let values: Vec<bool> = compiled.variables().iter().map(|v| model[v]).collect();
+            assert!(compiled.evaluate(&values), "Model does not satisfy formula");
+        }
+
+        Solution::Sat(model)
+    }
+
+    pub fn is_satisfiable(&self) -> bool {
+        matches!(self.solve(), Solution::Sat(_))
+    }
+}
@@ -0,0 +1,91 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Mutex, OnceLock};
+
+// Global table mapping variable names to their index, names are leaked so that
+// symbols can hand out `&'static str` without holding the lock
+#[derive(Default)]
+struct Interner {
+    names: Vec<&'static str>,
+    ids: HashMap<&'static str, u32>,
+}
+
+fn interner() -> &'static Mutex<Interner> {
+    static INTERNER: OnceLock<Mutex<Interner>> = OnceLock::new();
+    INTERNER.get_or_init(|| Mutex::new(Interner::default()))
+}
+
+/// An interned variable name such as `A`, `enable`, `x_12` or `bus[3]`.
+///
+/// Symbols are cheap to copy, hash and compare for equality. They are ordered by name.
+///
+/// Names are interned in a global table for the life of the process and never freed, so
+/// reading untrusted input that keeps introducing new names grows memory without bound.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    pub fn new(name: &str) -> Symbol {
+        let mut interner = interner().lock().unwrap();
+        if let Some(&id) = interner.ids.get(name) {
+            return Symbol(id);
+        }
+
+        let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+        let id = interner.names.len() as u32;
+        interner.names.push(name);
+        interner.ids.insert(name, id);
+        Symbol(id)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        interner().lock().unwrap().names[self.0 as usize]
+    }
+
+    // Single uppercase letters are written bare in RPN, every other name needs braces
+    pub(crate) fn is_letter(&self) -> bool {
+        let mut chars = self.as_str().chars();
+        matches!((chars.next(), chars.next()), (Some('A'..='Z'), None))
+    }
+}
+
+impl From<char> for Symbol {
+    fn from(c: char) -> Self {
+        Symbol::new(c.encode_utf8(&mut [0; 4]))
+    }
+}
+
+impl From<&str> for Symbol {
+    fn from(name: &str) -> Self {
+        Symbol::new(name)
+    }
+}
+
+impl PartialOrd for Symbol {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Symbol {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.0 == other.0 {
+            return Ordering::Equal;
+        }
+        let interner = interner().lock().unwrap();
+        interner.names[self.0 as usize].cmp(interner.names[other.0 as usize])
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl fmt::Debug for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Symbol({:?})", self.as_str())
+    }
+}
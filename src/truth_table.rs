@@ -0,0 +1,422 @@
+use crate::ast::{AstNode, Operator};
+use crate::error::Error;
+use crate::symbol::Symbol;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+// Word whose bit `i` is bit `shift` of `i`, the column of a variable within 64 rows
+const PATTERNS: [u64; 6] = [
+    0xAAAA_AAAA_AAAA_AAAA,
+    0xCCCC_CCCC_CCCC_CCCC,
+    0xF0F0_F0F0_F0F0_F0F0,
+    0xFF00_FF00_FF00_FF00,
+    0xFFFF_0000_FFFF_0000,
+    0xFFFF_FFFF_0000_0000,
+];
+
+/// Truth table packed one row per bit: bit `i` is the value on the row whose bits spell
+/// `i`, the first variable being the most significant.
+///
+/// The operators `!`, `&`, `|` and `^` work over the sorted union of the variables of both
+/// tables and panic when it has more than [`TruthTable::MAX_VARIABLES`], which
+/// [`TruthTable::apply`] reports as an error instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruthTable {
+    variables: Vec<Symbol>,
+    words: Vec<u64>,
+}
+
+impl TruthTable {
+    /// Largest number of variables of a table, so that the 2^24 rows of the widest table
+    /// fit in 2 MiB.
+    pub const MAX_VARIABLES: usize = 24;
+
+    /// Evaluate `node` on every row at once, 64 rows per word operation.
+    ///
+    /// # Panics
+    ///
+    /// If a variable of `node` is missing from `variables`, or there are more than
+    /// [`TruthTable::MAX_VARIABLES`] of them, see [`TruthTable::try_new`].
+    pub fn new(node: &AstNode, variables: Vec<Symbol>) -> TruthTable {
+        TruthTable::try_new(node, variables).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Evaluate `node` on every row at once, or an error if a variable of `node` is missing
+    /// from `variables` or there are more than [`TruthTable::MAX_VARIABLES`] of them.
+    pub fn try_new(node: &AstNode, variables: Vec<Symbol>) -> Result<TruthTable, Error> {
+        check_size(variables.len())?;
+        if let Some(&name) = node
+            .get_variables()
+            .iter()
+            .find(|name| !variables.contains(name))
+        {
+            return Err(Error::UnboundVariable(name));
+        }
+        let mut table = TruthTable {
+            variables,
+            words: Vec::new(),
+        };
+        table.words = table.bits(node);
+        table.mask();
+        Ok(table)
+    }
+
+    // Table whose row `row` has the value `value(row)`
+    fn from_fn(variables: Vec<Symbol>, value: impl Fn(usize) -> bool) -> TruthTable {
+        let mut table = TruthTable {
+            variables,
+            words: Vec::new(),
+        };
+        table.words = vec![0; table.num_words()];
+        for row in 0..table.rows() {
+            if value(row) {
+                table.words[row / 64] |= 1 << (row % 64);
+            }
+        }
+        table
+    }
+
+    /// Read a table in the hexadecimal notation of ABC and Kitty: the rows as one number,
+    /// most significant digit first, with an optional `0x` or `0X` prefix. The last variable
+    /// is variable 0 of those tools. Lengths and error positions are counted in bytes.
+    pub fn from_hex(variables: Vec<Symbol>, hex: &str) -> Result<TruthTable, Error> {
+        check_size(variables.len())?;
+        let digits = hex
+            .strip_prefix("0x")
+            .or_else(|| hex.strip_prefix("0X"))
+            .unwrap_or(hex);
+        let mut table = TruthTable {
+            variables,
+            words: Vec::new(),
+        };
+        table.words = vec![0; table.num_words()];
+        let expected = table.hex_digits();
+        if digits.len() != expected {
+            return Err(Error::HexLength {
+                expected,
+                found: digits.len(),
+            });
+        }
+
+        let offset = hex.len() - digits.len();
+        for (i, c) in digits.char_indices() {
+            let invalid = Error::InvalidHex { pos: offset + i };
+            let nibble = c.to_digit(16).ok_or(invalid.clone())? as u64;
+            // The only digit of a table with fewer than 4 rows may not set the others
+            if table.rows() < 4 && nibble >> table.rows() != 0 {
+                return Err(invalid);
+            }
+            let bit = (expected - 1 - i) * 4;
+            table.words[bit / 64] |= nibble << (bit % 64);
+        }
+        Ok(table)
+    }
+
+    pub fn variables(&self) -> &[Symbol] {
+        &self.variables
+    }
+
+    pub fn rows(&self) -> usize {
+        1 << self.variables.len()
+    }
+
+    /// Value of row `row`.
+    pub fn value(&self, row: usize) -> bool {
+        assert!(row < self.rows(), "Row {} is out of range", row);
+        self.words[row / 64] >> (row % 64) & 1 == 1
+    }
+
+    /// Number of rows on which the table is true.
+    pub fn count_ones(&self) -> u64 {
+        self.words.iter().map(|w| w.count_ones() as u64).sum()
+    }
+
+    /// The packed rows, 64 per word starting with the least significant bit. Bits past
+    /// the last row are zero.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Indices of the rows on which the table is true, in increasing order.
+    pub fn minterms(&self) -> Vec<u64> {
+        ones(&self.words)
+    }
+
+    /// Indices of the rows on which the table is false, in increasing order.
+    pub fn maxterms(&self) -> Vec<u64> {
+        ones(&(!self).words)
+    }
+
+    /// Table of the function with `variable` fixed to `value`, over the other variables.
+    /// A variable the table does not have leaves it unchanged.
+    pub fn cofactor(&self, variable: Symbol, value: bool) -> TruthTable {
+        let Some(index) = self.variables.iter().position(|&v| v == variable) else {
+            return self.clone();
+        };
+        let shift = self.variables.len() - 1 - index;
+        let mut variables = self.variables.clone();
+        variables.remove(index);
+
+        // Put the fixed bit back into each row of the smaller table
+        let low = (1 << shift) - 1;
+        TruthTable::from_fn(variables, |row| {
+            self.value((row & !low) << 1 | (value as usize) << shift | row & low)
+        })
+    }
+
+    /// The table as hexadecimal digits, see [`TruthTable::from_hex`].
+    pub fn to_hex(&self) -> String {
+        (0..self.hex_digits())
+            .rev()
+            .map(|i| {
+                let nibble = self.words[i * 4 / 64] >> (i * 4 % 64) & 0xF;
+                char::from_digit(nibble as u32, 16).unwrap()
+            })
+            .collect()
+    }
+
+    /// Sum of minterms: a disjunction with one conjunction of all variables per true row.
+    pub fn to_ast(&self) -> AstNode {
+        let num_vars = self.variables.len();
+        let products = self
+            .minterms()
+            .into_iter()
+            .map(|minterm| {
+                let literals = self
+                    .variables
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &name)| {
+                        let variable = AstNode::Variable(name);
+                        if minterm >> (num_vars - 1 - i) & 1 == 1 {
+                            variable
+                        } else {
+                            AstNode::Not(Box::new(variable))
+                        }
+                    })
+                    .collect();
+                AstNode::right_fold(literals, Operator::And, true)
+            })
+            .collect();
+        AstNode::right_fold(products, Operator::Or, false)
+    }
+
+    fn hex_digits(&self) -> usize {
+        (self.rows() / 4).max(1)
+    }
+
+    fn num_words(&self) -> usize {
+        self.rows().div_ceil(64)
+    }
+
+    // Clear the bits past the last row, which only exist with fewer than 6 variables
+    fn mask(&mut self) {
+        if self.rows() < 64 {
+            self.words[0] &= (1 << self.rows()) - 1;
+        }
+    }
+
+    // The same function over `variables`, which include those of the table
+    fn extend(&self, variables: &[Symbol]) -> TruthTable {
+        let num_vars = variables.len();
+        let shifts: Vec<usize> = self
+            .variables
+            .iter()
+            .map(|v| num_vars - 1 - variables.iter().position(|w| w == v).unwrap())
+            .collect();
+        TruthTable::from_fn(variables.to_vec(), |row| {
+            let old = shifts
+                .iter()
+                .fold(0, |old, &shift| old << 1 | row >> shift & 1);
+            self.value(old)
+        })
+    }
+
+    /// Apply `op` row by row, over the sorted union of the variables of both tables, or an
+    /// error if the union has more than [`TruthTable::MAX_VARIABLES`] variables.
+    pub fn apply(&self, other: &TruthTable, op: &Operator) -> Result<TruthTable, Error> {
+        if self.variables != other.variables {
+            let mut variables: Vec<Symbol> = self
+                .variables
+                .iter()
+                .chain(&other.variables)
+                .copied()
+                .collect();
+            variables.sort();
+            variables.dedup();
+            check_size(variables.len())?;
+            return self.extend(&variables).apply(&other.extend(&variables), op);
+        }
+
+        let combine = word_op(op);
+        let mut table = TruthTable {
+            variables: self.variables.clone(),
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(&a, &b)| combine(a, b))
+                .collect(),
+        };
+        table.mask();
+        Ok(table)
+    }
+
+    fn column(&self, index: usize) -> Vec<u64> {
+        let shift = self.variables.len() - 1 - index;
+        if shift < 6 {
+            vec![PATTERNS[shift]; self.num_words()]
+        } else {
+            (0..self.num_words())
+                .map(|w| if w >> (shift - 6) & 1 == 1 { !0 } else { 0 })
+                .collect()
+        }
+    }
+
+    fn bits(&self, node: &AstNode) -> Vec<u64> {
+        match node {
+            AstNode::Constant(value) => vec![if *value { !0 } else { 0 }; self.num_words()],
+            AstNode::Variable(name) => {
+                let index = self
+                    .variables
+                    .iter()
+                    .position(|v| v == name)
+                    .unwrap_or_else(|| panic!("Variable {} has no column", name));
+                self.column(index)
+            }
+            AstNode::Not(child) => {
+                let mut words = self.bits(child);
+                words.iter_mut().for_each(|w| *w = !*w);
+                words
+            }
+            AstNode::BinaryOperator(op, left, right) => {
+                let mut words = self.bits(left);
+                let right = self.bits(right);
+                let combine = word_op(op);
+                for (w, r) in words.iter_mut().zip(right) {
+                    *w = combine(*w, r);
+                }
+                words
+            }
+        }
+    }
+}
+
+fn check_size(variables: usize) -> Result<(), Error> {
+    if variables > TruthTable::MAX_VARIABLES {
+        return Err(Error::TooManyVariables {
+            variables,
+            limit: TruthTable::MAX_VARIABLES,
+        });
+    }
+    Ok(())
+}
+
+// The operator on 64 rows at once
+fn word_op(op: &Operator) -> fn(u64, u64) -> u64 {
+    match op {
+        Operator::And => |a, b| a & b,
+        Operator::Or => |a, b| a | b,
+        Operator::Xor => |a, b| a ^ b,
+        Operator::Iff => |a, b| !(a ^ b),
+        Operator::Implies => |a, b| !a | b,
+    }
+}
+
+// Positions of the set bits of `words`
+fn ones(words: &[u64]) -> Vec<u64> {
+    let mut ones = Vec::new();
+    for (i, &word) in words.iter().enumerate() {
+        let mut word = word;
+        while word != 0 {
+            ones.push(i as u64 * 64 + word.trailing_zeros() as u64);
+            word &= word - 1;
+        }
+    }
+    ones
+}
+
+impl Not for &TruthTable {
+    type Output = TruthTable;
+
+    fn not(self) -> TruthTable {
+        let mut table = self.clone();
+        table.words.iter_mut().for_each(|w| *w = !*w);
+        table.mask();
+        table
+    }
+}
+
+impl Not for TruthTable {
+    type Output = TruthTable;
+
+    fn not(self) -> TruthTable {
+        !&self
+    }
+}
+
+impl BitAnd for &TruthTable {
+    type Output = TruthTable;
+
+    fn bitand(self, other: &TruthTable) -> TruthTable {
+        self.apply(other, &Operator::And)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl BitAnd for TruthTable {
+    type Output = TruthTable;
+
+    fn bitand(self, other: TruthTable) -> TruthTable {
+        &self & &other
+    }
+}
+
+impl BitOr for &TruthTable {
+    type Output = TruthTable;
+
+    fn bitor(self, other: &TruthTable) -> TruthTable {
+        self.apply(other, &Operator::Or)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl BitOr for TruthTable {
+    type Output = TruthTable;
+
+    fn bitor(self, other: TruthTable) -> TruthTable {
+        &self | &other
+    }
+}
+
+impl BitXor for &TruthTable {
+    type Output = TruthTable;
+
+    fn bitxor(self, other: &TruthTable) -> TruthTable {
+        self.apply(other, &Operator::Xor)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+}
+
+impl BitXor for TruthTable {
+    type Output = TruthTable;
+
+    fn bitxor(self, other: TruthTable) -> TruthTable {
+        &self ^ &other
+    }
+}
+
+impl AstNode {
+    /// Truth table over the sorted variables of the formula, see [`TruthTable::new`].
+    pub fn to_truth_table(&self) -> TruthTable {
+        self.try_to_truth_table()
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Truth table over the sorted variables of the formula, or an error if it has more
+    /// than [`TruthTable::MAX_VARIABLES`] of them.
+    pub fn try_to_truth_table(&self) -> Result<TruthTable, Error> {
+        let mut variables: Vec<Symbol> = self.get_variables().into_iter().collect();
+        variables.sort();
+        TruthTable::try_new(self, variables)
+    }
+}
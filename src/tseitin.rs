@@ -0,0 +1,240 @@
+use crate::ast::{AstNode, Operator};
+use crate::cnf::{Cnf, Lit};
+use crate::symbol::Symbol;
+use std::collections::HashMap;
+
+// Subformula represented by the auxiliary variable of constants
+static TRUE: AstNode = AstNode::Constant(true);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    // Every auxiliary variable is equivalent to its subformula
+    Tseitin,
+    // Only the direction of each definition required by the polarity of the subformula
+    // is encoded, which yields fewer clauses
+    PlaistedGreenbaum,
+}
+
+// Polarities a subformula occurs with, relative to the root
+#[derive(Clone, Copy, PartialEq)]
+enum Polarity {
+    Positive,
+    Negative,
+    Both,
+}
+
+impl Polarity {
+    fn flip(self) -> Polarity {
+        match self {
+            Polarity::Positive => Polarity::Negative,
+            Polarity::Negative => Polarity::Positive,
+            Polarity::Both => Polarity::Both,
+        }
+    }
+
+    // Whether `x → f` is needed
+    fn positive(self) -> bool {
+        self != Polarity::Negative
+    }
+
+    // Whether `f → x` is needed
+    fn negative(self) -> bool {
+        self != Polarity::Positive
+    }
+}
+
+/// Equisatisfiable CNF of a formula.
+#[derive(Debug, Clone)]
+pub struct Encoding<'a> {
+    pub cnf: Cnf,
+    // Variable of every variable of the formula
+    pub inputs: HashMap<Symbol, u32>,
+    // Auxiliary variables with the subformula they stand for, in creation order
+    pub definitions: Vec<(u32, &'a AstNode)>,
+}
+
+struct Encoder<'a> {
+    mode: Mode,
+    encoding: Encoding<'a>,
+    // Literal constrained to be true, created on the first constant
+    truth: Option<Lit>,
+}
+
+impl<'a> Encoder<'a> {
+    fn clause(&mut self, lits: Vec<Lit>) {
+        self.encoding.cnf.add_clause(lits);
+    }
+
+    fn define(&mut self, node: &'a AstNode) -> Lit {
+        let var = self.encoding.cnf.new_var();
+        self.encoding.definitions.push((var, node));
+        Lit::positive(var)
+    }
+
+    fn constant(&mut self, value: bool) -> Lit {
+        let truth = match self.truth {
+            Some(lit) => lit,
+            None => {
+                let lit = self.define(&TRUE);
+                self.clause(vec![lit]);
+                self.truth = Some(lit);
+                lit
+            }
+        };
+        if value {
+            truth
+        } else {
+            !truth
+        }
+    }
+
+    fn input(&mut self, name: Symbol) -> Lit {
+        let cnf = &mut self.encoding.cnf;
+        let var = *self
+            .encoding
+            .inputs
+            .entry(name)
+            .or_insert_with(|| cnf.new_var());
+        Lit::positive(var)
+    }
+
+    // Clauses of `x → a op b` and of `a op b → x`, as required by the polarity
+    fn definition(&mut self, op: &Operator, x: Lit, a: Lit, b: Lit, polarity: Polarity) {
+        let (forward, backward) = (polarity.positive(), polarity.negative());
+        match op {
+            Operator::And => {
+                if forward {
+                    self.clause(vec![!x, a]);
+                    self.clause(vec![!x, b]);
+                }
+                if backward {
+                    self.clause(vec![x, !a, !b]);
+                }
+            }
+            Operator::Or => {
+                if forward {
+                    self.clause(vec![!x, a, b]);
+                }
+                if backward {
+                    self.clause(vec![x, !a]);
+                    self.clause(vec![x, !b]);
+                }
+            }
+            Operator::Implies => {
+                if forward {
+                    self.clause(vec![!x, !a, b]);
+                }
+                if backward {
+                    self.clause(vec![x, a]);
+                    self.clause(vec![x, !b]);
+                }
+            }
+            Operator::Xor => {
+                if forward {
+                    self.clause(vec![!x, a, b]);
+                    self.clause(vec![!x, !a, !b]);
+                }
+                if backward {
+                    self.clause(vec![x, !a, b]);
+                    self.clause(vec![x, a, !b]);
+                }
+            }
+            Operator::Iff => {
+                if forward {
+                    self.clause(vec![!x, !a, b]);
+                    self.clause(vec![!x, a, !b]);
+                }
+                if backward {
+                    self.clause(vec![x, a, b]);
+                    self.clause(vec![x, !a, !b]);
+                }
+            }
+        }
+    }
+
+    // Return a literal equivalent to `node`, introducing one fresh variable per binary
+    // operator. The tree is walked in post-order with an explicit stack, so deep formulas
+    // do not exhaust the call stack.
+    fn encode(&mut self, node: &'a AstNode, polarity: Polarity) -> Lit {
+        let mut tasks = vec![Task::Visit(node, polarity)];
+        let mut lits: Vec<Lit> = Vec::new();
+
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Visit(node, polarity) => {
+                    let polarity = match self.mode {
+                        Mode::Tseitin => Polarity::Both,
+                        Mode::PlaistedGreenbaum => polarity,
+                    };
+                    match node {
+                        AstNode::Constant(value) => lits.push(self.constant(*value)),
+                        AstNode::Variable(name) => lits.push(self.input(*name)),
+                        AstNode::Not(child) => {
+                            tasks.push(Task::Finish(node, polarity));
+                            tasks.push(Task::Visit(child, polarity.flip()));
+                        }
+                        AstNode::BinaryOperator(op, left, right) => {
+                            let (left_polarity, right_polarity) = match op {
+                                Operator::And | Operator::Or => (polarity, polarity),
+                                Operator::Implies => (polarity.flip(), polarity),
+                                Operator::Xor | Operator::Iff => (Polarity::Both, Polarity::Both),
+                            };
+                            // The left operand is popped, and so encoded, first
+                            tasks.push(Task::Finish(node, polarity));
+                            tasks.push(Task::Visit(right, right_polarity));
+                            tasks.push(Task::Visit(left, left_polarity));
+                        }
+                    }
+                }
+                Task::Finish(node, polarity) => {
+                    let lit = match node {
+                        // Negation is free, it only flips the literal
+                        AstNode::Not(_) => !lits.pop().unwrap(),
+                        AstNode::BinaryOperator(op, _, _) => {
+                            let b = lits.pop().unwrap();
+                            let a = lits.pop().unwrap();
+                            let x = self.define(node);
+                            self.definition(op, x, a, b, polarity);
+                            x
+                        }
+                        _ => unreachable!("Only operators are finished"),
+                    };
+                    lits.push(lit);
+                }
+            }
+        }
+
+        lits.pop().expect("The root is encoded")
+    }
+}
+
+// Step of the post-order walk of `Encoder::encode`
+enum Task<'a> {
+    // Push the operands of the node, or the literal of a leaf
+    Visit(&'a AstNode, Polarity),
+    // Combine the literals of the operands, on top of the stack
+    Finish(&'a AstNode, Polarity),
+}
+
+impl AstNode {
+    /// Definitional CNF of the formula: each binary operator gets a fresh auxiliary variable
+    /// defined by a constant number of clauses, so the clause count is linear in the size of
+    /// the formula. The result is satisfiable exactly when the formula is, and the inputs of
+    /// any of its models satisfy the formula.
+    pub fn to_definitional_cnf(&self, mode: Mode) -> Encoding<'_> {
+        let mut encoder = Encoder {
+            mode,
+            encoding: Encoding {
+                cnf: Cnf::new(),
+                inputs: HashMap::new(),
+                definitions: Vec::new(),
+            },
+            truth: None,
+        };
+
+        let root = encoder.encode(self, Polarity::Positive);
+        encoder.clause(vec![root]);
+
+        encoder.encoding
+    }
+}
@@ -0,0 +1,201 @@
+#[cfg(test)]
+mod tests {
+    use boole::aig::{Aig, AigLit};
+    use boole::ast::AstNode;
+    use boole::error::AigerError;
+    use boole::symbol::Symbol;
+    use std::collections::HashMap;
+
+    const FORMULAS: [&str; 9] = [
+        "AB&!", "AB|C&", "AB>C=", "AB^C^", "AB=!C|", "AB|C|D|", "ABCD|&|", "A1&B0|^", "A!A&",
+    ];
+
+    fn build(formulas: &[&str]) -> (Aig, Vec<AstNode>) {
+        let mut aig = Aig::new();
+        let asts: Vec<AstNode> = formulas
+            .iter()
+            .map(|formula| AstNode::try_from(*formula).unwrap())
+            .collect();
+        for (i, ast) in asts.iter().enumerate() {
+            let lit = ast.to_aig(&mut aig);
+            aig.add_output(Symbol::new(&format!("f{}", i)), lit);
+        }
+        (aig, asts)
+    }
+
+    fn assert_matches(aig: &Aig, asts: &[AstNode]) {
+        for (values, _) in AstNode::try_from("ABCD&&&").unwrap().truth_table() {
+            let expected: Vec<bool> = asts
+                .iter()
+                .map(|ast| ast.evaluate(&values).unwrap())
+                .collect();
+            assert_eq!(aig.evaluate(&values).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_structural_hashing() {
+        let mut aig = Aig::new();
+        let a = aig.input(Symbol::from('A'));
+        let b = aig.input(Symbol::from('B'));
+        let ab = aig.and(a, b);
+        assert_eq!(aig.and(b, a), ab);
+        assert_eq!(aig.and(a, a), a);
+        assert_eq!(aig.and(a, !a), AigLit::FALSE);
+        assert_eq!(aig.and(a, AigLit::TRUE), a);
+        assert_eq!(aig.and(AigLit::FALSE, b), AigLit::FALSE);
+        assert_eq!(aig.input(Symbol::from('A')), a);
+        assert_eq!(aig.num_ands(), 1);
+    }
+
+    #[test]
+    fn test_operators() {
+        let (aig, asts) = build(&FORMULAS);
+        assert_eq!(aig.outputs().len(), FORMULAS.len());
+        assert_matches(&aig, &asts);
+
+        for (i, (_, lit)) in aig.outputs().iter().enumerate() {
+            let ast = aig.to_ast(*lit);
+            for (values, value) in asts[i].truth_table() {
+                assert_eq!(ast.evaluate(&values).unwrap(), value);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shared_logic() {
+        let (aig, _) = build(&["AB&C|", "AB&C|!"]);
+        assert_eq!(aig.num_ands(), 2);
+        let outputs = aig.outputs();
+        assert_eq!(outputs[0].1, !outputs[1].1);
+    }
+
+    #[test]
+    fn test_aag_format() {
+        let (aig, _) = build(&["AB&!", "BA&"]);
+        assert_eq!(
+            aig.to_aag(),
+            "aag 3 2 0 2 1\n2\n4\n7\n6\n6 4 2\ni0 A\ni1 B\no0 f0\no1 f1\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let (aig, asts) = build(&FORMULAS);
+
+        let ascii = Aig::parse(aig.to_aag().as_bytes()).unwrap();
+        assert_eq!(ascii.inputs(), aig.inputs());
+        assert_eq!(ascii.num_ands(), aig.num_ands());
+        assert_matches(&ascii, &asts);
+
+        let binary = Aig::parse(&aig.to_aig()).unwrap();
+        assert_eq!(binary.inputs(), aig.inputs());
+        assert_eq!(binary.to_aag(), aig.to_aag());
+        assert_matches(&binary, &asts);
+    }
+
+    #[test]
+    fn test_parse_unordered_gates() {
+        let aig = Aig::parse(b"aag 4 2 0 1 2\n2\n4\n9\n8 6 2\n6 4 3\nc\ncomment\n").unwrap();
+        let names: Vec<String> = aig.inputs().iter().map(|s| s.to_string()).collect();
+        assert_eq!(names, ["i0", "i1"]);
+        assert_eq!(aig.outputs()[0].0.to_string(), "o0");
+        // !(A & (B & !A)) is always true
+        for a in [false, true] {
+            for b in [false, true] {
+                let values = HashMap::from([(Symbol::new("i0"), a), (Symbol::new("i1"), b)]);
+                assert_eq!(aig.evaluate(&values).unwrap(), [true]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Aig::parse(b"aag 1 1\n").unwrap_err(),
+            AigerError::InvalidHeader
+        );
+        assert_eq!(
+            Aig::parse(b"aag 2 1 1 0 0\n2\n4 5\n").unwrap_err(),
+            AigerError::Latches { count: 1 }
+        );
+        assert_eq!(
+            Aig::parse(b"aag 1 1 0 1 0\n2\nx\n").unwrap_err(),
+            AigerError::InvalidLine { line: 3 }
+        );
+        assert_eq!(
+            Aig::parse(b"aag 3 1 0 1 1\n2\n4\n4 6 2\n").unwrap_err(),
+            AigerError::UndefinedLiteral { literal: 6 }
+        );
+        assert_eq!(
+            Aig::parse(b"aag 3 1 0 1 2\n2\n4\n4 6 2\n6 4 2\n").unwrap_err(),
+            AigerError::Cycle { literal: 4 }
+        );
+        assert_eq!(
+            Aig::parse(b"aag 1 1 0 1 0\n2\n").unwrap_err(),
+            AigerError::UnexpectedEnd
+        );
+        assert_eq!(
+            Aig::parse(b"aig 3 2 0 1 1\n6\n\x02").unwrap_err(),
+            AigerError::UnexpectedEnd
+        );
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        // A delta of more than 32 bits
+        assert_eq!(
+            Aig::parse(b"aig 1 0 0 0 1\n\xff\xff\xff\xff\xff\xff\x01").unwrap_err(),
+            AigerError::Overflow
+        );
+        assert_eq!(
+            Aig::parse(b"aig 1 0 0 0 1\n\xff\xff\xff\xff\x10\x00").unwrap_err(),
+            AigerError::Overflow
+        );
+        // Counts beyond the maximum variable index or the length of the file
+        assert_eq!(
+            Aig::parse(b"aig 4294967295 4294967295 0 0 1\n").unwrap_err(),
+            AigerError::InvalidHeader
+        );
+        assert_eq!(
+            Aig::parse(b"aig 4294967295 4294967294 0 1 0\n").unwrap_err(),
+            AigerError::UnexpectedEnd
+        );
+        assert_eq!(
+            Aig::parse(b"aag 4294967295 0 0 4294967295 0\n").unwrap_err(),
+            AigerError::UnexpectedEnd
+        );
+        assert_eq!(
+            Aig::parse("aag 1 1 0 0 0\n2\né0 x\n".as_bytes()).unwrap_err(),
+            AigerError::InvalidLine { line: 3 }
+        );
+        // Variables defined twice
+        assert_eq!(
+            Aig::parse(b"aag 2 2 0 0 0\n2\n2\n").unwrap_err(),
+            AigerError::Redefined { literal: 2 }
+        );
+        assert_eq!(
+            Aig::parse(b"aag 2 1 0 1 1\n2\n2\n2 4 5\n").unwrap_err(),
+            AigerError::Redefined { literal: 2 }
+        );
+        assert_eq!(
+            Aig::parse(b"aag 3 1 0 1 2\n2\n4\n4 2 3\n4 3 2\n").unwrap_err(),
+            AigerError::Redefined { literal: 4 }
+        );
+    }
+
+    #[test]
+    fn test_parse_binary_inputs() {
+        // Binary inputs take no bytes, so there may be more of them than bytes in the file
+        let aig = Aig::parse(b"aig 5 5 0 1 0\n2\n").unwrap();
+        assert_eq!(aig.inputs().len(), 5);
+        assert_eq!(aig.outputs().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_non_utf8_symbols() {
+        let aig = Aig::parse(b"aag 2 2 0 0 0\n2\n4\ni0 \xff\ni1 B\n").unwrap();
+        let names: Vec<String> = aig.inputs().iter().map(|s| s.to_string()).collect();
+        assert_eq!(names, ["i0", "B"]);
+    }
+}
@@ -0,0 +1,134 @@
+#[cfg(test)]
+mod tests {
+    use boole::anf::Anf;
+    use boole::ast::AstNode;
+    use boole::bdd::BddManager;
+    use boole::boole::algebraic_normal_form;
+    use boole::error::Error;
+    use boole::symbol::Symbol;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_anf() {
+        let cases = [
+            ("AB^", "AB^"),
+            ("AB&", "AB&"),
+            ("A!", "1A^"),
+            ("AB|", "ABAB&^^"),
+            ("AB=", "1AB^^"),
+            ("AB>", "1AAB&^^"),
+            ("AB&AC&BC&||", "AB&AC&BC&^^"),
+            ("AA!|", "1"),
+            ("AA!&", "0"),
+        ];
+        for (formula, expected) in cases {
+            assert_eq!(algebraic_normal_form(formula), expected, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_degree() {
+        let degree = |formula: &str| {
+            AstNode::try_from(formula)
+                .unwrap()
+                .to_anf()
+                .unwrap()
+                .degree()
+        };
+        assert_eq!(degree("AA!|"), 0);
+        assert_eq!(degree("ABCD^^^"), 1);
+        assert_eq!(degree("AB|"), 2);
+        // A conjunction of n variables has degree n, a disjunction too
+        assert_eq!(degree("ABCDE&&&&"), 5);
+        assert_eq!(degree("ABCDE||||"), 5);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut manager = BddManager::new();
+        for formula in ["AB>C=D^", "ABC&&D|", "AB=CD=&E|", "AB|C!&D>E&"] {
+            let ast = AstNode::try_from(formula).unwrap();
+            let anf = ast.to_anf().unwrap();
+            assert_eq!(
+                anf.to_ast().to_bdd(&mut manager),
+                ast.to_bdd(&mut manager),
+                "{}",
+                formula
+            );
+            assert_eq!(anf.to_ast().to_anf(), Ok(anf));
+        }
+    }
+
+    #[test]
+    fn test_too_many_variables() {
+        let formula: String =
+            (0..21).map(|i| format!("{{v{}}}", i)).collect::<String>() + &"^".repeat(20);
+        let ast = AstNode::try_from(formula.as_str()).unwrap();
+        assert_eq!(
+            ast.to_anf(),
+            Err(Error::TooManyVariables {
+                variables: 21,
+                limit: Anf::MAX_VARIABLES
+            })
+        );
+    }
+
+    #[test]
+    fn test_cancellation() {
+        let variables = vec![Symbol::from('A'), Symbol::from('B')];
+        let anf = Anf::new(variables, vec![0b11, 0b10, 0b11, 0b00]);
+        assert_eq!(anf.monomials(), &[0b00, 0b10]);
+        assert_eq!(anf.to_rpn(), "1A^");
+    }
+
+    #[test]
+    fn test_sbox() {
+        // PRESENT S-box
+        let sbox: [u8; 16] = [
+            0xC, 0x5, 0x6, 0xB, 0x9, 0x0, 0xA, 0xD, 0x3, 0xE, 0xF, 0x8, 0x4, 0x7, 0x1, 0x2,
+        ];
+        let variables: Vec<Symbol> = ["x3", "x2", "x1", "x0"]
+            .iter()
+            .map(|name| Symbol::new(name))
+            .collect();
+
+        let components: Vec<Anf> = (0..4)
+            .map(|bit| {
+                let values: Vec<bool> = sbox.iter().map(|y| y >> bit & 1 == 1).collect();
+                Anf::from_truth_table(variables.clone(), &values).unwrap()
+            })
+            .collect();
+
+        // The S-box has algebraic degree 3 and its least significant bit only 2
+        assert_eq!(components.iter().map(Anf::degree).max(), Some(3));
+        assert_eq!(components[0].degree(), 2);
+        for (x, y) in sbox.iter().enumerate() {
+            let values: HashMap<Symbol, bool> = variables
+                .iter()
+                .enumerate()
+                .map(|(i, &name)| (name, x >> (3 - i) & 1 == 1))
+                .collect();
+            for (bit, component) in components.iter().enumerate() {
+                assert_eq!(component.evaluate(&values), Some(y >> bit & 1 == 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_truth_table_size() {
+        let variables: Vec<Symbol> = ['A', 'B'].into_iter().map(Symbol::from).collect();
+        assert_eq!(
+            Anf::from_truth_table(variables, &[true; 3]),
+            Err(Error::TableLength {
+                expected: 4,
+                found: 3
+            })
+        );
+
+        let variables: Vec<Symbol> = (0..64).map(|i| Symbol::new(&format!("x{}", i))).collect();
+        assert!(matches!(
+            Anf::from_truth_table(variables, &[]),
+            Err(Error::TooManyVariables { variables: 64, .. })
+        ));
+    }
+}
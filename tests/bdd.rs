@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::bdd::{Bdd, BddManager};
+    use boole::symbol::Symbol;
+
+    fn build(manager: &mut BddManager, formula: &str) -> Bdd {
+        AstNode::try_from(formula).unwrap().to_bdd(manager)
+    }
+
+    #[test]
+    fn test_canonical() {
+        let mut manager = BddManager::new();
+        // De Morgan, contraposition and the definition of XOR
+        let pairs = [
+            ("AB&!", "A!B!|"),
+            ("AB>", "B!A!>"),
+            ("AB^", "AB!&A!B&|"),
+            ("AB=", "AB^!"),
+            ("AB|C&", "AC&BC&|"),
+        ];
+        for (left, right) in pairs {
+            let left = build(&mut manager, left);
+            let right = build(&mut manager, right);
+            assert_eq!(left, right);
+        }
+        assert_ne!(build(&mut manager, "AB>"), build(&mut manager, "BA>"));
+    }
+
+    #[test]
+    fn test_constants() {
+        let mut manager = BddManager::new();
+        assert_eq!(build(&mut manager, "AA!|"), Bdd::TRUE);
+        assert_eq!(build(&mut manager, "AA!&"), Bdd::FALSE);
+        assert_eq!(build(&mut manager, "A1&"), build(&mut manager, "A"));
+        assert_eq!(build(&mut manager, "A0|!"), build(&mut manager, "A!"));
+    }
+
+    #[test]
+    fn test_sat_count() {
+        let mut manager = BddManager::new();
+        let f = build(&mut manager, "AB|C&");
+        assert_eq!(manager.sat_count(f), Some(3));
+        let g = build(&mut manager, "D");
+        // Counts cover every variable of the manager
+        assert_eq!(manager.sat_count(f), Some(6));
+        assert_eq!(manager.sat_count(g), Some(8));
+        assert_eq!(manager.sat_count(Bdd::TRUE), Some(16));
+        assert_eq!(manager.sat_count(Bdd::FALSE), Some(0));
+    }
+
+    #[test]
+    fn test_sat_count_large() {
+        let mut manager = BddManager::new();
+        // Parity of 100 variables has a linear size diagram and 2^99 models
+        let formula: String =
+            (0..100).map(|i| format!("{{x{}}}", i)).collect::<String>() + &"^".repeat(99);
+        let f = build(&mut manager, &formula);
+        assert_eq!(manager.sat_count(f), Some(1 << 99));
+        assert_eq!(manager.size(f), 2 * 99 + 1 + 2);
+
+        // 2^130 assignments do not fit in a u128
+        for i in 100..130 {
+            manager.var(Symbol::new(&format!("x{}", i)));
+        }
+        assert_eq!(manager.sat_count(Bdd::TRUE), None);
+        assert_eq!(manager.sat_count(Bdd::FALSE), Some(0));
+        assert_eq!(manager.sat_count(f), None);
+        // Fixing three variables leaves 2^127, which still fits
+        let g = build(&mut manager, "{x0}{x1}{x2}&&");
+        assert_eq!(manager.sat_count(g), Some(1 << 127));
+    }
+
+    #[test]
+    fn test_restrict_and_quantify() {
+        let mut manager = BddManager::new();
+        let f = build(&mut manager, "AB&C|");
+        let a = Symbol::from('A');
+
+        let f1 = manager.restrict(f, a, true);
+        assert_eq!(f1, build(&mut manager, "BC|"));
+        let f0 = manager.restrict(f, a, false);
+        assert_eq!(f0, build(&mut manager, "C"));
+
+        let exists = manager.exists(f, &[a]);
+        assert_eq!(exists, build(&mut manager, "BC|"));
+        let forall = manager.forall(f, &[a]);
+        assert_eq!(forall, build(&mut manager, "C"));
+
+        let all = [a, Symbol::from('B'), Symbol::from('C')];
+        assert_eq!(manager.exists(f, &all), Bdd::TRUE);
+        assert_eq!(manager.forall(f, &all), Bdd::FALSE);
+    }
+
+    #[test]
+    fn test_cubes() {
+        let mut manager = BddManager::new();
+        let ast = AstNode::try_from("AB^C|").unwrap();
+        let f = ast.to_bdd(&mut manager);
+
+        let cubes = manager.cubes(f);
+        let count: u128 = cubes
+            .iter()
+            .map(|cube| 1 << (manager.variables().len() - cube.len()))
+            .sum();
+        // Cubes are disjoint, so their sizes add up to the model count
+        assert_eq!(Some(count), manager.sat_count(f));
+        for cube in &cubes {
+            let mut assignment = cube.clone();
+            for &name in manager.variables() {
+                assignment.entry(name).or_insert(false);
+            }
+            assert_eq!(ast.evaluate(&assignment), Ok(true));
+        }
+
+        let model = manager.any_sat(f).unwrap();
+        assert!(cubes.contains(&model));
+        assert_eq!(manager.any_sat(Bdd::FALSE), None);
+    }
+
+    #[test]
+    fn test_to_ast() {
+        let mut manager = BddManager::new();
+        for formula in ["AB^C|", "AB>C=", "ABC&&", "A!B|", "AA!|", "0"] {
+            let f = build(&mut manager, formula);
+            let ast = manager.to_ast(f);
+            assert_eq!(ast.to_bdd(&mut manager), f, "{}", formula);
+        }
+        let a = build(&mut manager, "A");
+        assert_eq!(manager.to_ast(a).to_rpn(), "A");
+    }
+
+    #[test]
+    fn test_order() {
+        let names: Vec<Symbol> = ["a1", "a2", "a3", "b1", "b2", "b3"]
+            .iter()
+            .map(|name| Symbol::new(name))
+            .collect();
+        let formula = "{a1}{b1}={a2}{b2}={a3}{b3}=&&";
+
+        // Interleaving related variables keeps the diagram small
+        let mut interleaved =
+            BddManager::with_order(&[names[0], names[3], names[1], names[4], names[2], names[5]]);
+        let f = build(&mut interleaved, formula);
+        let mut separated = BddManager::with_order(&names);
+        let g = build(&mut separated, formula);
+
+        assert!(interleaved.size(f) < separated.size(g));
+        assert_eq!(interleaved.sat_count(f), separated.sat_count(g));
+    }
+}
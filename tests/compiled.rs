@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::{AstNode, Operator};
+    use boole::compiled::Instruction;
+    use std::collections::HashMap;
+
+    const FORMULAS: [&str; 8] = [
+        "AB&!", "AB|C&", "AB>C=", "AB^C^", "AB=!C|", "AB|C|D|", "ABCD|&|", "A1&B0|^",
+    ];
+
+    #[test]
+    fn test_matches_evaluation() {
+        for formula in FORMULAS {
+            let ast = AstNode::try_from(formula).unwrap();
+            let compiled = ast.compile();
+            let num_vars = compiled.variables().len();
+            for mask in 0..1u64 << num_vars {
+                let values: Vec<bool> = (0..num_vars).map(|i| mask >> i & 1 == 1).collect();
+                let assignment: HashMap<_, _> = compiled
+                    .variables()
+                    .iter()
+                    .copied()
+                    .zip(values.iter().copied())
+                    .collect();
+                let expected = ast.evaluate(&assignment).unwrap();
+                assert_eq!(compiled.evaluate(&values), expected, "{}", formula);
+                assert_eq!(compiled.evaluate_mask(mask), expected, "{}", formula);
+            }
+        }
+    }
+
+    #[test]
+    fn test_columns_match_truth_table() {
+        for formula in FORMULAS {
+            let ast = AstNode::try_from(formula).unwrap();
+            let compiled = ast.compile();
+            let num_vars = compiled.variables().len();
+            // Column of variable `i` in truth table order, first variable most significant
+            let columns: Vec<u64> = (0..num_vars)
+                .map(|i| {
+                    (0..1u64 << num_vars)
+                        .filter(|row| row >> (num_vars - 1 - i) & 1 == 1)
+                        .fold(0, |column, row| column | 1 << row)
+                })
+                .collect();
+            let mask = (1u64 << (1 << num_vars)) - 1;
+            assert_eq!(
+                compiled.evaluate_columns(&columns) & mask,
+                ast.to_truth_table().words()[0],
+                "{}",
+                formula
+            );
+        }
+    }
+
+    #[test]
+    fn test_program() {
+        let compiled = AstNode::try_from("AB&!C|").unwrap().compile();
+        let program: Vec<String> = compiled
+            .instructions()
+            .iter()
+            .map(|i| i.to_string())
+            .collect();
+        assert_eq!(
+            program,
+            [
+                "r0 = v0",
+                "r1 = v1",
+                "r0 = r0 & r1",
+                "r0 = !r0",
+                "r1 = v2",
+                "r0 = r0 | r1"
+            ]
+        );
+        assert_eq!(
+            compiled.instructions()[2],
+            Instruction::BinaryOperator {
+                op: Operator::And,
+                dst: 0,
+                left: 0,
+                right: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_registers() {
+        // The deeper operand goes first, so chains need two registers whatever their side
+        let formula = "A".to_string() + &('B'..='Z').collect::<String>() + &"&".repeat(25);
+        let right = AstNode::try_from(formula.as_str()).unwrap().compile();
+        assert_eq!(right.registers(), 2);
+        let left: String = ('B'..='Z').fold("A".to_string(), |f, c| format!("{}{}|", f, c));
+        assert_eq!(
+            AstNode::try_from(left.as_str())
+                .unwrap()
+                .compile()
+                .registers(),
+            2
+        );
+
+        // A balanced tree of 8 leaves needs four
+        let balanced = AstNode::try_from("AB^CD^^EF^GH^^^").unwrap().compile();
+        assert_eq!(balanced.registers(), 4);
+        assert!(balanced.evaluate_mask(0b1));
+        assert!(!balanced.evaluate_mask(0b11));
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::boole::{conjunctive_normal_form, evaluate_set, negation_normal_form, sat};
+
+    #[test]
+    fn test_constant_parse() {
+        assert_eq!(AstNode::try_from("A1&").unwrap().to_rpn(), "A1&");
+        assert_eq!(AstNode::try_from("0").unwrap().to_rpn(), "0");
+        assert_eq!(AstNode::from_infix("A ∧ ⊤ ∨ 0").unwrap().to_rpn(), "A1&0|");
+    }
+
+    #[test]
+    fn test_constant_nnf() {
+        assert_eq!(negation_normal_form("A1&"), "A");
+        assert_eq!(negation_normal_form("A0|"), "A");
+        assert_eq!(negation_normal_form("A0&"), "0");
+        assert_eq!(negation_normal_form("A1|!"), "0");
+        assert_eq!(negation_normal_form("0!"), "1");
+        assert_eq!(negation_normal_form("A0>"), "A!");
+        assert_eq!(negation_normal_form("A1="), "A");
+        assert_eq!(negation_normal_form("A1^"), "A!");
+    }
+
+    #[test]
+    fn test_constant_cnf() {
+        assert_eq!(conjunctive_normal_form("A0|B&"), "AB&");
+        assert_eq!(conjunctive_normal_form("AB&1|C|"), "1");
+        assert_eq!(conjunctive_normal_form("AB&C0&|"), "AB&");
+    }
+
+    #[test]
+    fn test_constant_sat() {
+        assert!(sat("1"));
+        assert!(!sat("0"));
+        assert!(!sat("A0&"));
+        assert!(sat("A1&"));
+    }
+
+    #[test]
+    fn test_constant_truth_table() {
+        let table = AstNode::try_from("A1&").unwrap().truth_table();
+        let results: Vec<bool> = table.into_iter().map(|(_, result)| result).collect();
+        assert_eq!(results, vec![false, true]);
+    }
+
+    #[test]
+    fn test_constant_set() {
+        assert_eq!(evaluate_set("A1&", vec![vec![0, 1]]), vec![0, 1]);
+        assert_eq!(evaluate_set("A0|", vec![vec![0, 1]]), vec![0, 1]);
+        assert_eq!(evaluate_set("A0&", vec![vec![0, 1]]), vec![]);
+        assert_eq!(
+            evaluate_set("AB&1!|", vec![vec![0, 1], vec![1, 2]]),
+            vec![1]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::{AstNode, Operator};
+    use boole::dag::{Dag, Node};
+    use boole::symbol::Symbol;
+
+    const FORMULAS: [&str; 8] = [
+        "AB&!", "AB|C&", "AB>C=", "AB^C^", "AB=!C|", "AB|C|D|", "ABCD|&|", "A1&B0|^",
+    ];
+
+    #[test]
+    fn test_hash_consing() {
+        let mut dag = Dag::new();
+        let a = AstNode::try_from("AB&").unwrap().to_dag(&mut dag);
+        let b = AstNode::try_from("AB&").unwrap().to_dag(&mut dag);
+        assert_eq!(a, b);
+        assert_eq!(dag.len(), 3);
+
+        let x = dag.variable(Symbol::from('A'));
+        let y = dag.variable(Symbol::from('B'));
+        assert_eq!(dag.binary(Operator::And, x, y), a);
+        assert_ne!(dag.binary(Operator::And, y, x), a);
+        assert_eq!(dag.node(a), &Node::BinaryOperator(Operator::And, x, y));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut dag = Dag::new();
+        for formula in FORMULAS {
+            let ast = AstNode::try_from(formula).unwrap();
+            let id = ast.to_dag(&mut dag);
+            assert_eq!(dag.to_ast(id), ast);
+        }
+    }
+
+    #[test]
+    fn test_nnf_matches_tree() {
+        let mut dag = Dag::new();
+        for formula in FORMULAS {
+            let ast = AstNode::try_from(formula).unwrap();
+            let id = ast.to_dag(&mut dag);
+            let nnf = dag.to_nnf(id);
+            assert_eq!(dag.to_ast(nnf), ast.to_nnf(), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_cnf_matches_tree() {
+        let mut dag = Dag::new();
+        for formula in FORMULAS {
+            let ast = AstNode::try_from(formula).unwrap();
+            let id = ast.to_dag(&mut dag);
+            let cnf = dag.to_cnf(id);
+            assert_eq!(
+                dag.to_ast(cnf).to_rpn(),
+                ast.to_cnf().to_rpn(),
+                "{}",
+                formula
+            );
+        }
+    }
+
+    // x_{i+1} = (x_i ∧ v_i) ↔ (x_i ∨ v_i): every level refers to the previous one twice, so the tree
+    // doubles in size with each level while the DAG grows by a constant
+    fn chain(dag: &mut Dag, levels: usize) -> usize {
+        let mut node = dag.variable(Symbol::new("v0"));
+        for i in 1..=levels {
+            let v = dag.variable(Symbol::new(&format!("v{}", i)));
+            let both = dag.binary(Operator::And, node, v);
+            let either = dag.binary(Operator::Or, node, v);
+            node = dag.binary(Operator::Iff, both, either);
+        }
+        let nnf = dag.to_nnf(node);
+        dag.size(nnf)
+    }
+
+    #[test]
+    fn test_shared_nnf() {
+        let mut dag = Dag::new();
+        // 40 levels would be a tree of more than 2^40 nodes
+        let size = chain(&mut dag, 40);
+        assert!(size < 40 * 20, "{}", size);
+    }
+}
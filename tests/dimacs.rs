@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::cnf::Lit;
+    use boole::dimacs::Dimacs;
+    use boole::error::DimacsError;
+    use boole::solver::{Solution, Solver};
+    use boole::symbol::Symbol;
+
+    #[test]
+    fn test_dimacs_parse() {
+        let input = "c example\nc var 1 A\np cnf 3 2\n1 -3 0\n2 3\n-1 0\n";
+        let dimacs = Dimacs::parse(input).unwrap();
+        assert_eq!(dimacs.cnf.num_vars(), 3);
+        assert_eq!(
+            dimacs.cnf.clauses(),
+            &[
+                vec![Lit::positive(0), Lit::negative(2)],
+                vec![Lit::positive(1), Lit::positive(2), Lit::negative(0)],
+            ]
+        );
+        assert_eq!(dimacs.names[&0], Symbol::from('A'));
+        assert_eq!(dimacs.to_ast().to_rpn(), "A{x3}!|{x2}{x3}A!||&");
+    }
+
+    #[test]
+    fn test_dimacs_satlib_trailer() {
+        let input = "p cnf 2 2\n 1 2 0\n-1 0\n%\n0\n";
+        let dimacs = Dimacs::parse(input).unwrap();
+        let mut solver = Solver::from_cnf(&dimacs.cnf);
+        assert!(solver.solve());
+        assert_eq!(solver.model(), &[false, true]);
+    }
+
+    #[test]
+    fn test_dimacs_write() {
+        let dimacs = Dimacs::parse("p cnf 2 2\n1 -2 0\n2 0\n").unwrap();
+        assert_eq!(dimacs.to_string(), "p cnf 2 2\n1 -2 0\n2 0\n");
+    }
+
+    #[test]
+    fn test_dimacs_round_trip() {
+        let ast = AstNode::try_from("AB^C>").unwrap();
+        let dimacs = ast.to_dimacs();
+        let parsed = Dimacs::parse(&dimacs.to_string()).unwrap();
+        assert_eq!(parsed, dimacs);
+
+        // The encoding is equisatisfiable and keeps the input names
+        match parsed.to_ast().solve() {
+            Solution::Sat(model) => assert_eq!(ast.evaluate(&model), Ok(true)),
+            Solution::Unsat => panic!("encoding should be satisfiable"),
+        }
+        assert_eq!(
+            AstNode::try_from("AA!&")
+                .unwrap()
+                .to_dimacs()
+                .to_ast()
+                .solve(),
+            Solution::Unsat
+        );
+    }
+
+    #[test]
+    fn test_dimacs_unnamed_variables() {
+        // Variable 3 is unnamed but its default name is taken by variable 1
+        let dimacs = Dimacs::parse("c var 1 x3\np cnf 3 1\n1 3 0\n").unwrap();
+        assert_eq!(dimacs.to_ast().to_rpn(), "{x3}{x3'}|");
+
+        let ast = AstNode::try_from("{x3}{x1}&").unwrap();
+        let encoded = ast.to_dimacs().to_ast();
+        let variables = encoded.get_variables();
+        assert_eq!(variables.len(), ast.to_dimacs().cnf.num_vars() as usize);
+        match encoded.solve() {
+            Solution::Sat(model) => assert_eq!(ast.evaluate(&model), Ok(true)),
+            Solution::Unsat => panic!("encoding should be satisfiable"),
+        }
+    }
+
+    #[test]
+    fn test_dimacs_errors() {
+        assert_eq!(Dimacs::parse("1 2 0\n"), Err(DimacsError::MissingHeader));
+        assert_eq!(
+            Dimacs::parse("p cnf x 1\n"),
+            Err(DimacsError::InvalidHeader { line: 1 })
+        );
+        assert_eq!(
+            Dimacs::parse("p cnf 2 1\n1 a 0\n"),
+            Err(DimacsError::InvalidLiteral {
+                line: 2,
+                token: "a".to_string()
+            })
+        );
+        assert_eq!(
+            Dimacs::parse("p cnf 2 1\n1 -3 0\n"),
+            Err(DimacsError::VariableOutOfRange { line: 2, var: 3 })
+        );
+        assert_eq!(
+            Dimacs::parse("p cnf 2 2\n1 2 0\n"),
+            Err(DimacsError::ClauseCount {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Dimacs::parse("p cnf 2 1\n1 2\n"),
+            Err(DimacsError::UnterminatedClause)
+        );
+        assert_eq!(
+            Dimacs::parse("p cnf 4000000000 0\n"),
+            Err(DimacsError::TooManyVariables {
+                line: 1,
+                count: 4_000_000_000
+            })
+        );
+        assert_eq!(
+            Dimacs::parse("c var 1 a\nc var 2 a\np cnf 2 1\n1 -2 0\n"),
+            Err(DimacsError::DuplicateName {
+                line: 2,
+                name: "a".to_string()
+            })
+        );
+        // Repeating the same name for the same variable is harmless
+        assert!(Dimacs::parse("c var 1 a\nc var 1 a\np cnf 1 1\n1 0\n").is_ok());
+    }
+}
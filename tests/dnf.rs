@@ -0,0 +1,59 @@
+mod tests {
+    use boole::boole::disjunctive_normal_form;
+
+    #[test]
+    fn test_dnf_0() {
+        let dnf = disjunctive_normal_form("AB&!");
+        let result = "A!B!|";
+        assert_eq!(result, dnf)
+    }
+
+    #[test]
+    fn test_dnf_1() {
+        let dnf = disjunctive_normal_form("AB|!");
+        let result = "A!B!&";
+        assert_eq!(result, dnf)
+    }
+
+    #[test]
+    fn test_dnf_2() {
+        let dnf = disjunctive_normal_form("AB|C&");
+        let result = "AC&BC&|";
+        assert_eq!(result, dnf)
+    }
+
+    #[test]
+    fn test_dnf_3() {
+        let dnf = disjunctive_normal_form("AB|C|D|");
+        let result = "ABCD|||";
+        assert_eq!(result, dnf)
+    }
+
+    #[test]
+    fn test_dnf_4() {
+        let dnf = disjunctive_normal_form("AB&C&D&");
+        let result = "ABCD&&&";
+        assert_eq!(result, dnf)
+    }
+
+    #[test]
+    fn test_dnf_5() {
+        let dnf = disjunctive_normal_form("AB&!C!|");
+        let result = "A!B!C!||";
+        assert_eq!(result, dnf)
+    }
+
+    #[test]
+    fn test_dnf_6() {
+        let dnf = disjunctive_normal_form("AB|!C!&");
+        let result = "A!B!C!&&";
+        assert_eq!(result, dnf)
+    }
+
+    #[test]
+    fn test_dnf_7() {
+        let dnf = disjunctive_normal_form("ABCD|&|");
+        let result = "ABC&BD&||";
+        assert_eq!(result, dnf)
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::equivalence::Verdict;
+
+    fn ast(formula: &str) -> AstNode {
+        AstNode::try_from(formula).unwrap()
+    }
+
+    #[test]
+    fn test_normal_forms() {
+        for formula in ["AB>C=", "ABC&&D|!", "AB=CD=&", "AB|C!&D>", "AB^C^"] {
+            let ast = ast(formula);
+            assert!(ast.equivalent(&ast.to_nnf()).holds(), "{}", formula);
+            assert!(ast.equivalent(&ast.to_cnf()).holds(), "{}", formula);
+            assert!(ast.equivalent(&ast.to_dnf()).holds(), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_counterexample() {
+        let left = ast("AB>");
+        let right = ast("BA>");
+        let verdict = left.equivalent(&right);
+        let assignment = verdict.counterexample().unwrap();
+        assert_ne!(left.evaluate(assignment), right.evaluate(assignment));
+    }
+
+    #[test]
+    fn test_entails() {
+        assert!(ast("AB&").entails(&ast("A")).holds());
+        assert!(ast("AAB>&").entails(&ast("B")).holds());
+        assert!(ast("AA!&").entails(&ast("B")).holds());
+
+        let verdict = ast("AB|").entails(&ast("A"));
+        let assignment = verdict.counterexample().unwrap();
+        assert_eq!(ast("AB|").evaluate(assignment), Ok(true));
+        assert_eq!(ast("A").evaluate(assignment), Ok(false));
+    }
+
+    #[test]
+    fn test_tautology_and_contradiction() {
+        assert_eq!(ast("AA!|").is_tautology(), Verdict::Holds);
+        assert_eq!(ast("AB>A>A>").is_tautology(), Verdict::Holds);
+        assert_eq!(ast("AA!&").is_contradiction(), Verdict::Holds);
+
+        let verdict = ast("AB|").is_tautology();
+        assert_eq!(
+            ast("AB|").evaluate(verdict.counterexample().unwrap()),
+            Ok(false)
+        );
+        let verdict = ast("AB^").is_contradiction();
+        assert_eq!(
+            ast("AB^").evaluate(verdict.counterexample().unwrap()),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn test_many_variables() {
+        // Two orderings of a 200-variable parity, far beyond truth table enumeration
+        let names: Vec<String> = (0..200).map(|i| format!("{{x{}}}", i)).collect();
+        let forward = names.concat() + &"^".repeat(199);
+        let backward: String = names.iter().rev().cloned().collect::<String>() + &"^".repeat(199);
+        let forward = ast(&forward);
+        let backward = ast(&backward);
+        assert!(forward.equivalent(&backward).holds());
+
+        let flipped = AstNode::Not(Box::new(backward));
+        let verdict = forward.equivalent(&flipped);
+        assert_eq!(verdict.counterexample().unwrap().len(), 200);
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use boole::boole::{
+        try_conjunctive_normal_form, try_evaluate_set, try_negation_normal_form,
+        try_print_truth_table, try_sat,
+    };
+    use boole::error::{Error, ParseError};
+    use boole::symbol::Symbol;
+
+    #[test]
+    fn test_try_valid_formulas() {
+        assert_eq!(try_negation_normal_form("AB&!"), Ok("A!B!|".to_string()));
+        assert_eq!(try_conjunctive_normal_form("AB|!"), Ok("A!B!&".to_string()));
+        assert_eq!(try_sat("AA!&"), Ok(false));
+        assert_eq!(
+            try_evaluate_set("AB&", vec![vec![0, 1], vec![1, 2]]),
+            Ok(vec![1])
+        );
+        assert_eq!(try_print_truth_table("AB|"), Ok(()));
+    }
+
+    #[test]
+    fn test_try_parse_errors() {
+        let err = Error::Parse(ParseError::MissingOperand {
+            pos: 1,
+            operator: '&',
+        });
+        assert_eq!(try_negation_normal_form("A&"), Err(err.clone()));
+        assert_eq!(try_conjunctive_normal_form("A&"), Err(err.clone()));
+        assert_eq!(try_sat("A&"), Err(err.clone()));
+        assert_eq!(try_print_truth_table("A&"), Err(err));
+    }
+
+    #[test]
+    fn test_try_evaluate_set_count() {
+        let result = try_evaluate_set("AB&", vec![vec![0, 1]]);
+        assert_eq!(
+            result,
+            Err(Error::SetCount {
+                variables: 2,
+                sets: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_evaluate_set_unbound() {
+        let result = try_evaluate_set("AC&", vec![vec![0, 1], vec![1]]);
+        assert_eq!(result, Err(Error::UnboundVariable(Symbol::from('C'))));
+    }
+}
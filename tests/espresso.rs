@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::bdd::BddManager;
+    use boole::error::Error;
+    use boole::espresso::{complement, minimize};
+    use boole::qm::{minimal_cover, Implicant};
+
+    fn equivalent(left: &AstNode, right: &AstNode) -> bool {
+        let mut manager = BddManager::new();
+        left.to_bdd(&mut manager) == right.to_bdd(&mut manager)
+    }
+
+    fn minterms(cubes: &[u64]) -> Vec<Implicant> {
+        cubes.iter().map(|&m| Implicant::minterm(m)).collect()
+    }
+
+    #[test]
+    fn test_complement() {
+        let cover = [Implicant {
+            value: 0b100,
+            mask: 0b011,
+        }];
+        let result = complement(3, &cover).unwrap();
+        assert_eq!(
+            result,
+            vec![Implicant {
+                value: 0,
+                mask: 0b011
+            }]
+        );
+        assert_eq!(
+            complement(3, &[]).unwrap(),
+            vec![Implicant {
+                value: 0,
+                mask: 0b111
+            }]
+        );
+
+        let cover = minterms(&[0, 3, 5, 6]);
+        let result = complement(3, &cover).unwrap();
+        for m in 0..8 {
+            let inside = result.iter().any(|c| c.covers(m));
+            assert_eq!(inside, ![0, 3, 5, 6].contains(&m));
+        }
+    }
+
+    #[test]
+    fn test_matches_exact() {
+        let cases: [(&[u64], &[u64]); 4] = [
+            (&[4, 8, 10, 11, 12, 15], &[9, 14]),
+            (&[0, 1, 2, 5, 6, 7], &[]),
+            (&[0, 2, 5, 7, 8, 10, 13, 15], &[]),
+            (&[1, 3, 7, 11, 15], &[0, 2, 5]),
+        ];
+        for (on, dont_care) in cases {
+            let cover = minimize(4, &minterms(on), &minterms(dont_care)).unwrap();
+            let exact = minimal_cover(4, on, dont_care).unwrap();
+            assert_eq!(cover.len(), exact.len(), "{:?} {:?}", on, cover);
+            for m in 0..16 {
+                let inside = cover.iter().any(|c| c.covers(m));
+                if on.contains(&m) {
+                    assert!(inside);
+                } else if !dont_care.contains(&m) {
+                    assert!(!inside);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_functions() {
+        // Linear congruential generator so the functions are the same on every run
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            state >> 33
+        };
+
+        for _ in 0..50 {
+            let (mut on, mut dont_care, mut off) = (Vec::new(), Vec::new(), Vec::new());
+            for m in 0..64 {
+                match next() % 5 {
+                    0 | 1 => on.push(m),
+                    2 => dont_care.push(m),
+                    _ => off.push(m),
+                }
+            }
+
+            let cover = minimize(6, &minterms(&on), &minterms(&dont_care)).unwrap();
+            for &m in &on {
+                assert!(cover.iter().any(|c| c.covers(m)));
+            }
+            for &m in &off {
+                assert!(!cover.iter().any(|c| c.covers(m)));
+            }
+            // Every cube is prime: raising any of its literals reaches the off-set
+            for cube in &cover {
+                for bit in (0..6).map(|i| 1u64 << i).filter(|bit| cube.mask & bit == 0) {
+                    let raised = Implicant {
+                        value: cube.value & !bit,
+                        mask: cube.mask | bit,
+                    };
+                    assert!(off.iter().any(|&m| raised.covers(m)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_espresso() {
+        let cases = [
+            ("AB&AB!&|", "A"),
+            ("AB|AC|&", "ABC&|"),
+            ("AB^A|", "AB|"),
+            ("AA!&", "0"),
+            ("AA!|", "1"),
+        ];
+        for (formula, expected) in cases {
+            let ast = AstNode::try_from(formula).unwrap();
+            assert_eq!(ast.espresso().unwrap().to_rpn(), expected, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_wide_decoder() {
+        // Enable of a 32-bit address decoder for the ranges 0x8000_0000..0x8000_00ff and
+        // 0x8000_0100..0x8000_01ff, far beyond the reach of exact minimization
+        let bit = |i: usize| format!("{{a{:02}}}", i);
+        let range = |prefix: u64| {
+            let literals: Vec<String> = (8..32)
+                .map(|i| {
+                    if prefix >> (i - 8) & 1 == 1 {
+                        bit(i)
+                    } else {
+                        bit(i) + "!"
+                    }
+                })
+                .collect();
+            literals.concat() + &"&".repeat(23)
+        };
+        let formula = range(0x80_0000) + &range(0x80_0001) + "|";
+        let ast = AstNode::try_from(formula.as_str()).unwrap();
+
+        let minimized = ast.espresso().unwrap();
+        assert!(equivalent(&ast, &minimized));
+        // The two ranges merge into a single cube on the 23 high bits
+        let (variables, cover) = minimized.to_cover().unwrap();
+        assert_eq!(variables.len(), 23);
+        assert_eq!(cover.len(), 1);
+        assert_eq!(cover[0].literal_count(23), 23);
+    }
+
+    #[test]
+    fn test_equivalent() {
+        for formula in ["AB>C=D^", "ABC&&D|", "AB=CD=&E|", "ABCDE^^^^", "AB|C!&D>E&"] {
+            let ast = AstNode::try_from(formula).unwrap();
+            let minimized = ast.espresso().unwrap();
+            assert!(equivalent(&ast, &minimized), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_too_many_variables() {
+        let formula: String =
+            (0..65).map(|i| format!("{{v{}}}", i)).collect::<String>() + &"|".repeat(64);
+        let ast = AstNode::try_from(formula.as_str()).unwrap();
+        assert_eq!(
+            ast.to_cover().unwrap_err(),
+            Error::TooManyVariables {
+                variables: 65,
+                limit: 64
+            }
+        );
+        assert!(ast.espresso().is_err());
+
+        let error = Error::TooManyVariables {
+            variables: 65,
+            limit: 64,
+        };
+        assert_eq!(complement(65, &[]), Err(error.clone()));
+        assert_eq!(minimize(65, &minterms(&[0]), &[]), Err(error));
+        assert_eq!(complement(64, &[]).unwrap()[0].mask, u64::MAX);
+    }
+}
@@ -1,4 +1,5 @@
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use boole::boole::eval_formula;
 
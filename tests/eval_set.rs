@@ -1,6 +1,20 @@
 #[cfg(test)]
 mod tests {
-    use boole::boole::evaluate_set;
+    use boole::ast::AstNode;
+    use boole::boole::{
+        evaluate_set, evaluate_set_in, evaluate_set_in_sorted, evaluate_set_of,
+        evaluate_set_of_sorted, try_evaluate_set_in,
+    };
+    use boole::error::Error;
+    use boole::symbol::Symbol;
+    use std::collections::{BTreeSet, HashMap, HashSet};
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    enum Role {
+        Admin,
+        Editor,
+        Viewer,
+    }
 
     #[test]
     fn test_not_operator() {
@@ -39,4 +53,90 @@ mod tests {
         let result = evaluate_set(formula, sets);
         assert_eq!(result, vec![0, 1]);
     }
+
+    #[test]
+    fn test_strings() {
+        let admins = vec!["alice".to_string(), "bob".to_string()];
+        let banned = vec!["bob".to_string(), "carol".to_string()];
+        let result = evaluate_set_of_sorted("AB!&", vec![admins, banned]);
+        assert_eq!(result, BTreeSet::from(["alice".to_string()]));
+    }
+
+    #[test]
+    fn test_hashes() {
+        let sets = vec![vec![u64::MAX, 7], vec![7, 1 << 40]];
+        assert_eq!(
+            evaluate_set_of_sorted("AB^", sets),
+            BTreeSet::from([1 << 40, u64::MAX])
+        );
+    }
+
+    #[test]
+    fn test_unordered_elements() {
+        let ast = AstNode::try_from("{staff}{read_only}>").unwrap();
+        let sets = HashMap::from([
+            (
+                Symbol::new("staff"),
+                HashSet::from([Role::Admin, Role::Editor]),
+            ),
+            (Symbol::new("read_only"), HashSet::from([Role::Viewer])),
+        ]);
+        let universe = HashSet::from([Role::Admin, Role::Editor, Role::Viewer]);
+        assert_eq!(
+            ast.evaluate_set_of(&sets, &universe),
+            Ok(HashSet::from([Role::Viewer]))
+        );
+        assert_eq!(
+            ast.evaluate_set_in(&sets, &universe),
+            Ok(HashSet::from([Role::Viewer]))
+        );
+    }
+
+    #[test]
+    fn test_unordered_string_api() {
+        let staff = vec![Role::Admin, Role::Editor];
+        let read_only = vec![Role::Viewer, Role::Editor];
+        assert_eq!(
+            evaluate_set_of("AB^", vec![staff, read_only]),
+            HashSet::from([Role::Admin, Role::Viewer])
+        );
+    }
+
+    #[test]
+    fn test_explicit_universe() {
+        let sets = HashMap::from([(Symbol::from('A'), vec![0, 1, 2])]);
+        assert_eq!(
+            evaluate_set_in_sorted("A!", vec![0, 1, 2, 3, 4], sets),
+            BTreeSet::from([3, 4])
+        );
+        assert_eq!(
+            evaluate_set_in_sorted("1", vec![2, 1], HashMap::new()),
+            BTreeSet::from([1, 2])
+        );
+    }
+
+    #[test]
+    fn test_named_sets() {
+        let sets = HashMap::from([
+            (Symbol::from('A'), vec![0, 1, 2]),
+            (Symbol::from('C'), vec![1, 2, 3]),
+        ]);
+        assert_eq!(
+            evaluate_set_in("AC&", vec![0, 1, 2, 3], sets),
+            HashSet::from([1, 2])
+        );
+    }
+
+    #[test]
+    fn test_set_binding_errors() {
+        let sets = HashMap::from([(Symbol::from('A'), vec![0, 1])]);
+        assert_eq!(
+            try_evaluate_set_in("AB|", vec![0, 1], sets.clone()),
+            Err(Error::UnboundVariable(Symbol::from('B')))
+        );
+        assert_eq!(
+            try_evaluate_set_in("A!", vec![0], sets),
+            Err(Error::OutsideUniverse(Symbol::from('A')))
+        );
+    }
 }
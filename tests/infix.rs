@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::error::ParseError;
+    use boole::symbol::Symbol;
+
+    fn rpn(formula: &str) -> String {
+        AstNode::from_infix(formula).unwrap().to_rpn()
+    }
+
+    #[test]
+    fn test_infix_variable() {
+        assert_eq!(rpn("A"), "A");
+    }
+
+    #[test]
+    fn test_infix_precedence() {
+        assert_eq!(rpn("A | B & C"), "ABC&|");
+        assert_eq!(rpn("!A & B"), "A!B&");
+        assert_eq!(rpn("A ^ B | C"), "ABC|^");
+        assert_eq!(rpn("A -> B ^ C"), "ABC^>");
+        assert_eq!(rpn("A <-> B -> C"), "ABC>=");
+    }
+
+    #[test]
+    fn test_infix_associativity() {
+        assert_eq!(rpn("A & B & C"), "AB&C&");
+        assert_eq!(rpn("A -> B -> C"), "ABC>>");
+    }
+
+    #[test]
+    fn test_infix_parentheses() {
+        assert_eq!(rpn("(A | B) & C"), "AB|C&");
+        assert_eq!(rpn("!(A & B)"), "AB&!");
+        assert_eq!(rpn("((A))"), "A");
+    }
+
+    #[test]
+    fn test_infix_unicode() {
+        assert_eq!(rpn("¬A ∧ B ∨ C"), "A!B&C|");
+        assert_eq!(rpn("(A ⊕ B) → C ↔ D"), "AB^C>D=");
+    }
+
+    #[test]
+    fn test_infix_matches_rpn() {
+        let infix = AstNode::from_infix("!(A | B) & C").unwrap();
+        let rpn = AstNode::try_from("AB|!C&").unwrap();
+        assert_eq!(infix.to_cnf().to_rpn(), rpn.to_cnf().to_rpn());
+    }
+
+    #[test]
+    fn test_infix_errors() {
+        assert!(AstNode::from_infix("").is_err());
+        assert!(AstNode::from_infix("A &").is_err());
+        assert!(AstNode::from_infix("(A | B").is_err());
+        assert!(AstNode::from_infix("A B").is_err());
+        assert!(AstNode::from_infix("A - B").is_err());
+        assert!(AstNode::from_infix("A & 2").is_err());
+    }
+
+    #[test]
+    fn test_infix_depth() {
+        let nested = "(".repeat(1000) + "A" + &")".repeat(1000);
+        assert_eq!(
+            AstNode::from_infix(&nested),
+            Ok(AstNode::Variable(Symbol::from('A')))
+        );
+
+        // Reported at the token opening the level past the limit, instead of overflowing
+        let negations = "!".repeat(50_000) + "A";
+        assert_eq!(
+            AstNode::from_infix(&negations),
+            Err(ParseError::TooDeep { pos: 1000 })
+        );
+        let implications = "A -> ".repeat(50_000) + "A";
+        assert_eq!(
+            AstNode::from_infix(&implications),
+            Err(ParseError::TooDeep { pos: 5002 })
+        );
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::error::Error;
+    use boole::kmap::KarnaughMap;
+    use boole::qm::minimal_cover;
+    use boole::symbol::Symbol;
+    use std::collections::HashMap;
+
+    fn map(formula: &str) -> KarnaughMap {
+        KarnaughMap::new(&AstNode::try_from(formula).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_render_three() {
+        let expected = "\
+A\\BC  00   01   11   10
+   0  0    1b   1b   0
+   1  0    1b   1ab  1a
+a: A & B
+b: C
+";
+        assert_eq!(map("AB&C|").to_string(), expected);
+    }
+
+    #[test]
+    fn test_render_four() {
+        let expected = "\
+AB\\CD  00   01   11   10
+   00  0    0    1b   0
+   01  0    0    1b   0
+   11  1a   1a   1ab  1a
+   10  0    0    1b   0
+a: A & B
+b: C & D
+";
+        assert_eq!(map("AB&CD&|").to_string(), expected);
+    }
+
+    #[test]
+    fn test_gray_order() {
+        let ast = AstNode::try_from("AB>C^DE&|F=").unwrap();
+        let map = KarnaughMap::new(&ast).unwrap();
+        assert_eq!((map.rows(), map.columns()), (8, 8));
+
+        let variables = map.variables().to_vec();
+        for row in 0..map.rows() {
+            for column in 0..map.columns() {
+                let m = map.minterm(row, column);
+                // Neighbouring cells differ in a single variable
+                let right = map.minterm(row, (column + 1) % map.columns());
+                let below = map.minterm((row + 1) % map.rows(), column);
+                assert_eq!((m ^ right).count_ones(), 1);
+                assert_eq!((m ^ below).count_ones(), 1);
+
+                let values: HashMap<Symbol, bool> = variables
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &name)| (name, m >> (5 - i) & 1 == 1))
+                    .collect();
+                assert_eq!(ast.evaluate(&values), Ok(map.value(row, column)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_groups_cover() {
+        let map = map("ABCD^^^E|");
+        for row in 0..map.rows() {
+            for column in 0..map.columns() {
+                let m = map.minterm(row, column);
+                let covered = map.groups().iter().any(|group| group.covers(m));
+                assert_eq!(covered, map.value(row, column));
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_groups() {
+        let minterms = AstNode::try_from("AB^")
+            .unwrap()
+            .to_truth_table()
+            .minterms();
+        let exact = minimal_cover(2, &minterms, &[]).unwrap();
+        let map = map("AB^").with_groups(exact.clone());
+        assert_eq!(map.groups(), exact.as_slice());
+        assert!(map.to_string().ends_with("a: A & !B\nb: !A & B\n"));
+    }
+
+    #[test]
+    fn test_size() {
+        let one = AstNode::try_from("AA!|").unwrap();
+        assert_eq!(
+            KarnaughMap::new(&one).unwrap_err(),
+            Error::MapSize { variables: 1 }
+        );
+        let seven = AstNode::try_from("ABCDEFG&&&&&&").unwrap();
+        assert_eq!(
+            KarnaughMap::new(&seven).unwrap_err(),
+            Error::MapSize { variables: 7 }
+        );
+        // Reported as a map size before any truth table is built
+        let wide: String =
+            (0..30).map(|i| format!("{{v{}}}", i)).collect::<String>() + &"&".repeat(29);
+        let wide = AstNode::try_from(wide.as_str()).unwrap();
+        assert_eq!(
+            KarnaughMap::new(&wide).unwrap_err(),
+            Error::MapSize { variables: 30 }
+        );
+    }
+}
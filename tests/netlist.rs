@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use boole::aig::Aig;
+    use boole::ast::{AstNode, Operator};
+    use boole::boole::{adder, multiplier};
+    use boole::error::Error;
+    use boole::netlist::Netlist;
+
+    #[test]
+    fn test_adder() {
+        let netlist = Netlist::adder(4).unwrap();
+        assert_eq!(netlist.inputs().len(), 8);
+        assert_eq!(netlist.outputs().len(), 5);
+        // A half adder and three full adders
+        assert_eq!(netlist.gate_count(), 2 + 3 * 5);
+        for a in 0..16 {
+            for b in 0..16 {
+                assert_eq!(netlist.evaluate(a, b), adder(a, b));
+            }
+        }
+
+        let wide = Netlist::adder(32).unwrap();
+        assert_eq!(wide.outputs().len(), 32);
+        for (a, b) in [
+            (u32::MAX, 1),
+            (0x8000_0000, 0x8000_0000),
+            (123_456_789, 987_654_321),
+        ] {
+            assert_eq!(wide.evaluate(a, b), adder(a, b));
+        }
+    }
+
+    #[test]
+    fn test_multiplier() {
+        let netlist = Netlist::multiplier(4).unwrap();
+        assert_eq!(netlist.outputs().len(), 8);
+        for a in 0..16 {
+            for b in 0..16 {
+                assert_eq!(netlist.evaluate(a, b), multiplier(a, b));
+            }
+        }
+
+        let wide = Netlist::multiplier(16).unwrap();
+        for (a, b) in [(0xFFFF, 0xFFFF), (1234, 4321), (0, 777)] {
+            assert_eq!(wide.evaluate(a, b), multiplier(a, b));
+        }
+    }
+
+    #[test]
+    fn test_width() {
+        assert_eq!(
+            Netlist::adder(0).unwrap_err(),
+            Error::OperandWidth { width: 0 }
+        );
+        assert_eq!(
+            Netlist::multiplier(33).unwrap_err(),
+            Error::OperandWidth { width: 33 }
+        );
+    }
+
+    #[test]
+    fn test_output_formulas() {
+        let netlist = Netlist::multiplier(2).unwrap();
+        for (i, ast) in netlist.to_ast().iter().enumerate() {
+            for a in 0..4 {
+                for b in 0..4 {
+                    let values = netlist.assignment(a, b);
+                    let expected = multiplier(a, b) >> i & 1 == 1;
+                    assert_eq!(ast.evaluate(&values), Ok(expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solver() {
+        let netlist = Netlist::adder(3).unwrap();
+        let outputs = netlist.to_ast();
+        for (a, b) in [(0, 0), (3, 5), (7, 7), (6, 1)] {
+            let inputs = netlist
+                .assignment(a, b)
+                .into_iter()
+                .map(|(name, value)| {
+                    let variable = AstNode::Variable(name);
+                    if value {
+                        variable
+                    } else {
+                        AstNode::Not(Box::new(variable))
+                    }
+                })
+                .reduce(|l, r| AstNode::BinaryOperator(Operator::And, Box::new(l), Box::new(r)))
+                .unwrap();
+            for (i, output) in outputs.iter().enumerate() {
+                let verdict = if adder(a, b) >> i & 1 == 1 {
+                    inputs.entails(output)
+                } else {
+                    inputs.entails(&AstNode::Not(Box::new(output.clone())))
+                };
+                assert!(verdict.holds());
+            }
+        }
+    }
+
+    #[test]
+    fn test_aig_export() {
+        let netlist = Netlist::multiplier(3).unwrap();
+        let aig = Aig::parse(&netlist.to_aig().to_aig()).unwrap();
+        assert_eq!(aig.inputs(), netlist.inputs());
+        for a in 0..8 {
+            for b in 0..8 {
+                let bits = aig.evaluate(&netlist.assignment(a, b)).unwrap();
+                let product = bits
+                    .iter()
+                    .enumerate()
+                    .fold(0, |p, (i, &bit)| p | (bit as u32) << i);
+                assert_eq!(product, multiplier(a, b));
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::boole::eval_formula;
+    use boole::error::ParseError;
+
+    #[test]
+    fn test_unknown_token() {
+        let err = AstNode::try_from("AB&x").unwrap_err();
+        assert_eq!(err, ParseError::UnknownToken { pos: 3, token: 'x' });
+    }
+
+    #[test]
+    fn test_missing_operand() {
+        let err = AstNode::try_from("A&B").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::MissingOperand {
+                pos: 1,
+                operator: '&'
+            }
+        );
+        assert_eq!(
+            eval_formula("!").unwrap_err(),
+            ParseError::MissingOperand {
+                pos: 0,
+                operator: '!'
+            }
+        );
+    }
+
+    #[test]
+    fn test_leftover_operands() {
+        let err = AstNode::try_from("AB!C&").unwrap_err();
+        assert_eq!(err, ParseError::LeftoverOperands { pos: 1, count: 2 });
+        assert_eq!(
+            eval_formula("10&11").unwrap_err(),
+            ParseError::LeftoverOperands { pos: 3, count: 3 }
+        );
+    }
+
+    #[test]
+    fn test_empty_input() {
+        assert_eq!(AstNode::try_from("").unwrap_err(), ParseError::EmptyInput);
+        assert_eq!(eval_formula("").unwrap_err(), ParseError::EmptyInput);
+        assert_eq!(
+            AstNode::from_infix("  ").unwrap_err(),
+            ParseError::EmptyInput
+        );
+    }
+
+    #[test]
+    fn test_infix_errors() {
+        assert_eq!(
+            AstNode::from_infix("(A | B").unwrap_err(),
+            ParseError::UnclosedParenthesis { pos: 0 }
+        );
+        assert_eq!(
+            AstNode::from_infix("A & ").unwrap_err(),
+            ParseError::UnexpectedEnd { pos: 4 }
+        );
+        assert_eq!(
+            AstNode::from_infix("A B").unwrap_err(),
+            ParseError::UnexpectedToken { pos: 2, token: 'B' }
+        );
+        assert_eq!(
+            AstNode::from_infix("A - B").unwrap_err(),
+            ParseError::UnknownToken { pos: 2, token: '-' }
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let err = AstNode::try_from("AB&&").unwrap_err();
+        assert_eq!(
+            err.render("AB&&"),
+            "AB&&\n   ^ missing operand for operator '&'"
+        );
+    }
+
+    #[test]
+    fn test_render_unicode() {
+        let err = AstNode::from_infix("¬A ∧ ?").unwrap_err();
+        assert_eq!(err.render("¬A ∧ ?"), "¬A ∧ ?\n     ^ unknown token '?'");
+    }
+}
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::bdd::BddManager;
+    use boole::error::PlaError;
+    use boole::pla::Pla;
+    use boole::qm::Implicant;
+    use boole::symbol::Symbol;
+
+    const FULL_ADDER: &str = "\
+# full adder
+.i 3
+.o 2
+.ilb a b cin
+.ob sum cout
+.p 7
+001 10
+010 10
+100 10
+111 11
+011 01
+101 01
+110 01
+.e
+";
+
+    #[test]
+    fn test_parse() {
+        let pla = Pla::parse(FULL_ADDER).unwrap();
+        assert_eq!(
+            pla.inputs,
+            vec![Symbol::new("a"), Symbol::new("b"), Symbol::new("cin")]
+        );
+        assert_eq!(pla.outputs, vec![Symbol::new("sum"), Symbol::new("cout")]);
+        assert_eq!(pla.on[0].len(), 4);
+        assert_eq!(pla.on[1].len(), 4);
+        assert!(pla.on[0].contains(&Implicant::minterm(0b111)));
+        assert!(pla.dont_care.iter().all(|cover| cover.is_empty()));
+    }
+
+    #[test]
+    fn test_default_names() {
+        let pla = Pla::parse(".i 2\n.o 1\n1- 1\n-1 -\n").unwrap();
+        assert_eq!(pla.inputs, vec![Symbol::new("x0"), Symbol::new("x1")]);
+        assert_eq!(pla.outputs, vec![Symbol::new("f0")]);
+        assert_eq!(
+            pla.on[0],
+            vec![Implicant {
+                value: 0b10,
+                mask: 0b01
+            }]
+        );
+        assert_eq!(
+            pla.dont_care[0],
+            vec![Implicant {
+                value: 0b01,
+                mask: 0b10
+            }]
+        );
+    }
+
+    #[test]
+    fn test_minimize() {
+        let pla = Pla::parse(FULL_ADDER).unwrap().minimize().unwrap();
+        // The sum is irreducible, the carry is the majority of three
+        assert_eq!(pla.on[0].len(), 4);
+        assert_eq!(pla.on[1].len(), 3);
+
+        let outputs = pla.to_ast();
+        let expected = [
+            AstNode::from_infix("a ^ b ^ cin").unwrap(),
+            AstNode::from_infix("a & b | a & cin | b & cin").unwrap(),
+        ];
+        let mut manager = BddManager::new();
+        for (output, expected) in outputs.iter().zip(&expected) {
+            assert_eq!(output.to_bdd(&mut manager), expected.to_bdd(&mut manager));
+        }
+    }
+
+    #[test]
+    fn test_off_set() {
+        // Only 00 is on and only 11 is off, so a single literal covers the function
+        let pla = Pla::parse(".i 2\n.o 1\n.type fr\n00 1\n11 0\n.e\n").unwrap();
+        let minimized = pla.minimize().unwrap();
+        assert_eq!(minimized.on[0].len(), 1);
+        assert_eq!(minimized.on[0][0].literal_count(2), 1);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let pla = Pla::parse(FULL_ADDER).unwrap().minimize().unwrap();
+        let text = pla.to_string();
+        assert!(text.starts_with(".i 3\n.o 2\n.ilb a b cin\n.ob sum cout\n"));
+        assert!(text.ends_with(".e\n"));
+        assert_eq!(Pla::parse(&text).unwrap(), pla);
+
+        let ast = AstNode::try_from("AB&C|").unwrap();
+        let pla = ast.to_pla().unwrap();
+        assert_eq!(Pla::parse(&pla.to_string()).unwrap(), pla);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(Pla::parse("1-0 1\n"), Err(PlaError::MissingHeader));
+        assert_eq!(
+            Pla::parse(".i x\n"),
+            Err(PlaError::InvalidDirective { line: 1 })
+        );
+        assert_eq!(
+            Pla::parse(".i 2\n.o 1\n.ilb a\n"),
+            Err(PlaError::InvalidDirective { line: 3 })
+        );
+        assert_eq!(
+            Pla::parse(".i 2\n.o 1\n1x 1\n"),
+            Err(PlaError::InvalidTerm { line: 3 })
+        );
+        assert_eq!(
+            Pla::parse(".i 2\n.o 1\n1 1\n"),
+            Err(PlaError::InvalidTerm { line: 3 })
+        );
+        assert_eq!(
+            Pla::parse(".i 65\n"),
+            Err(PlaError::TooManyInputs { inputs: 65 })
+        );
+        assert_eq!(
+            Pla::parse(".i 2\n.o 1\n.p 2\n11 1\n.e\n"),
+            Err(PlaError::TermCount {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+}
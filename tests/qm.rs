@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::bdd::BddManager;
+    use boole::error::Error;
+    use boole::qm::{
+        minimal_cover, minimal_pos, minimal_sop, prime_implicants, Implicant, MAX_VARIABLES,
+    };
+    use boole::symbol::Symbol;
+
+    fn letters(n: usize) -> Vec<Symbol> {
+        ('A'..='Z').take(n).map(Symbol::from).collect()
+    }
+
+    fn equivalent(left: &AstNode, right: &AstNode) -> bool {
+        let mut manager = BddManager::new();
+        left.to_bdd(&mut manager) == right.to_bdd(&mut manager)
+    }
+
+    #[test]
+    fn test_prime_implicants() {
+        // f(A, B, C) = Σm(0, 1, 2, 5, 6, 7) has six primes of two literals each
+        let primes = prime_implicants(3, &[0, 1, 2, 5, 6, 7], &[]).unwrap();
+        assert_eq!(primes.len(), 6);
+        assert!(primes.iter().all(|p| p.literal_count(3) == 2));
+        assert!(primes.contains(&Implicant { value: 0, mask: 1 }));
+        assert!(primes.contains(&Implicant { value: 5, mask: 2 }));
+    }
+
+    #[test]
+    fn test_cyclic_core() {
+        // No essential prime, Petrick's method picks one of the two covers of three terms
+        let minterms = [0, 1, 2, 5, 6, 7];
+        let cover = minimal_cover(3, &minterms, &[]).unwrap();
+        assert_eq!(cover.len(), 3);
+        for m in 0..8 {
+            assert_eq!(cover.iter().any(|p| p.covers(m)), minterms.contains(&m));
+        }
+    }
+
+    #[test]
+    fn test_dont_cares() {
+        // f(A, B, C, D) = Σm(4, 8, 10, 11, 12, 15) + d(9, 14)
+        let minterms = [4, 8, 10, 11, 12, 15];
+        let dont_cares = [9, 14];
+        let cover = minimal_cover(4, &minterms, &dont_cares).unwrap();
+        assert_eq!(cover.len(), 3);
+        assert_eq!(cover.iter().map(|p| p.literal_count(4)).sum::<usize>(), 7);
+        for m in 0..16 {
+            if minterms.contains(&m) {
+                assert!(cover.iter().any(|p| p.covers(m)));
+            } else if !dont_cares.contains(&m) {
+                assert!(!cover.iter().any(|p| p.covers(m)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimal_sop() {
+        let cases = [
+            ("AB&AB!&|", "A"),
+            ("AB|AC|&", "ABC&|"),
+            ("AB&!A!B!&|", "A!B!|"),
+            ("AB^A|", "AB|"),
+            ("AA!&", "0"),
+            ("AA!|", "1"),
+        ];
+        for (formula, expected) in cases {
+            let ast = AstNode::try_from(formula).unwrap();
+            assert_eq!(ast.minimal_sop().unwrap().to_rpn(), expected, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_minimal_pos() {
+        let cases = [
+            ("ABC&|", "AB|AC|&"),
+            ("AB&AB!&|", "A"),
+            ("AB^", "A!B!|AB|&"),
+            ("AA!&", "0"),
+            ("AA!|", "1"),
+        ];
+        for (formula, expected) in cases {
+            let ast = AstNode::try_from(formula).unwrap();
+            assert_eq!(ast.minimal_pos().unwrap().to_rpn(), expected, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_equivalent() {
+        for formula in ["AB>C=D^", "ABC&&D|", "AB=CD=&", "ABCD^^^", "AB|C!&D>"] {
+            let ast = AstNode::try_from(formula).unwrap();
+            assert!(equivalent(&ast, &ast.minimal_sop().unwrap()), "{}", formula);
+            assert!(equivalent(&ast, &ast.minimal_pos().unwrap()), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_explicit_minterms() {
+        let variables = letters(4);
+        let sop = minimal_sop(&variables, &[4, 8, 10, 11, 12, 15], &[9, 14]).unwrap();
+        let pos = minimal_pos(&variables, &[4, 8, 10, 11, 12, 15], &[9, 14]).unwrap();
+        // Both agree with the function outside the don't-care set
+        let (_, sop_minterms) = sop.minterms().unwrap();
+        let (_, pos_minterms) = pos.minterms().unwrap();
+        for m in [4, 8, 10, 11, 12, 15] {
+            assert!(sop_minterms.contains(&m) && pos_minterms.contains(&m));
+        }
+        for m in [0, 1, 2, 3, 5, 6, 7, 13] {
+            assert!(!sop_minterms.contains(&m) && !pos_minterms.contains(&m));
+        }
+    }
+
+    #[test]
+    fn test_too_many_variables() {
+        let formula: String =
+            (0..70).map(|i| format!("{{x{}}}", i)).collect::<String>() + &"|".repeat(69);
+        let ast = AstNode::try_from(formula.as_str()).unwrap();
+        let error = Error::TooManyVariables {
+            variables: 70,
+            limit: MAX_VARIABLES,
+        };
+        assert_eq!(ast.minterms(), Err(error.clone()));
+        assert_eq!(ast.minimal_sop(), Err(error.clone()));
+        assert_eq!(ast.minimal_pos(), Err(error));
+        assert!(minimal_pos(&letters(21), &[0], &[]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_terms() {
+        assert_eq!(
+            minimal_cover(2, &[1, 4], &[]),
+            Err(Error::MintermRange {
+                minterm: 4,
+                variables: 2
+            })
+        );
+        assert_eq!(
+            prime_implicants(3, &[0], &[8]),
+            Err(Error::MintermRange {
+                minterm: 8,
+                variables: 3
+            })
+        );
+        assert_eq!(
+            prime_implicants(65, &[0], &[]),
+            Err(Error::TooManyVariables {
+                variables: 65,
+                limit: 64
+            })
+        );
+        assert!(minimal_sop(&letters(3), &[9], &[]).is_err());
+        assert_eq!(prime_implicants(64, &[u64::MAX], &[]).unwrap().len(), 1);
+    }
+}
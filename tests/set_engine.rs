@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::error::Error;
+    use boole::set_engine::{Bitset, SetEngine};
+    use boole::symbol::Symbol;
+    use std::collections::{HashMap, HashSet};
+
+    const FORMULAS: [&str; 8] = [
+        "AB&!", "AB|C&", "AB>C=", "AB^C^", "AB=!C|", "AB|C|", "ABC|&A!|", "A1&B0|^",
+    ];
+
+    #[test]
+    fn test_bitset() {
+        let mut a = Bitset::new(130);
+        let mut b = Bitset::full(130);
+        assert_eq!(b.count(), 130);
+        assert_eq!(b.capacity(), 130);
+        assert!(!b.contains(130));
+        assert!(a.is_empty());
+        assert!(!b.is_empty());
+
+        a.insert(0);
+        a.insert(64);
+        a.insert(129);
+        b.difference_with(&a).unwrap();
+        assert_eq!(b.count(), 127);
+        assert!(!b.contains(64));
+
+        b.union_with(&a).unwrap();
+        assert_eq!(b, Bitset::full(130));
+        b.symmetric_difference_with(&a).unwrap();
+        b.intersect_with(&a).unwrap();
+        assert!(b.is_empty());
+        assert_eq!(b.capacity(), 130);
+        assert!(b.ones().next().is_none());
+        assert_eq!(a.ones().collect::<Vec<_>>(), [0, 64, 129]);
+
+        // Sets of different capacities are not combined
+        assert_eq!(
+            a.union_with(&Bitset::full(64)),
+            Err(Error::CapacityMismatch {
+                capacity: 130,
+                other: 64
+            })
+        );
+        assert_eq!(a.count(), 3);
+    }
+
+    #[test]
+    fn test_matches_set_semantics() {
+        // Every combination of membership in A, B and C
+        let universe: Vec<u32> = (0..8).collect();
+        let engine = SetEngine::new(universe.clone());
+        let sets: HashMap<Symbol, Bitset> = ['A', 'B', 'C']
+            .iter()
+            .enumerate()
+            .map(|(i, &name)| {
+                let members: Vec<u32> = universe
+                    .iter()
+                    .filter(|&&x| x >> i & 1 == 1)
+                    .copied()
+                    .collect();
+                (Symbol::from(name), engine.bitset(&members).unwrap())
+            })
+            .collect();
+
+        for formula in FORMULAS {
+            let ast = AstNode::try_from(formula).unwrap();
+            let result = engine.evaluate(&ast, &sets).unwrap();
+            for &x in &universe {
+                let values = HashMap::from([
+                    (Symbol::from('A'), x & 1 == 1),
+                    (Symbol::from('B'), x >> 1 & 1 == 1),
+                    (Symbol::from('C'), x >> 2 & 1 == 1),
+                ]);
+                let index = engine.index(&x).unwrap();
+                assert_eq!(
+                    result.contains(index),
+                    ast.evaluate(&values).unwrap(),
+                    "{}",
+                    formula
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_elements() {
+        let engine = SetEngine::new(["read", "write", "delete", "read"]);
+        assert_eq!(engine.universe(), ["read", "write", "delete"]);
+        assert_eq!(engine.bitset(&["admin"]), None);
+
+        let sets = HashMap::from([(Symbol::from('A'), engine.bitset(&["write"]).unwrap())]);
+        let ast = AstNode::try_from("A!").unwrap();
+        let result = engine.evaluate(&ast, &sets).unwrap();
+        assert_eq!(
+            engine.elements(&result).collect::<Vec<_>>(),
+            [&"read", &"delete"]
+        );
+
+        let ast = AstNode::try_from("AB|").unwrap();
+        assert_eq!(
+            engine.evaluate(&ast, &sets),
+            Err(Error::UnboundVariable(Symbol::from('B')))
+        );
+
+        // A bitset of another engine is rejected before any operator runs
+        let sets = HashMap::from([
+            (Symbol::from('A'), engine.bitset(&["write"]).unwrap()),
+            (Symbol::from('B'), Bitset::full(2)),
+        ]);
+        assert_eq!(
+            engine.evaluate(&ast, &sets),
+            Err(Error::BitsetCapacity {
+                variable: Symbol::from('B'),
+                capacity: 2,
+                expected: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_large_universe() {
+        let n = 1 << 20;
+        let engine = SetEngine::new(0..n as u64);
+        let evens: Vec<u64> = (0..n as u64).step_by(2).collect();
+        let thirds: Vec<u64> = (0..n as u64).step_by(3).collect();
+        let sets = HashMap::from([
+            (Symbol::from('A'), engine.bitset(&evens).unwrap()),
+            (Symbol::from('B'), engine.bitset(&thirds).unwrap()),
+        ]);
+
+        // A deep chain only costs one pass over the words per operator
+        let formula = "AB|".to_string() + &"A&B|".repeat(200) + "!";
+        let ast = AstNode::try_from(formula.as_str()).unwrap();
+        let result = engine.evaluate(&ast, &sets).unwrap();
+        let expected = (0..n).filter(|x| x % 2 != 0 && x % 3 != 0).count();
+        assert_eq!(result.count(), expected);
+    }
+
+    #[test]
+    fn test_elements_outside_universe() {
+        let ast = AstNode::try_from("AB|").unwrap();
+        let sets = HashMap::from([
+            (Symbol::from('A'), HashSet::from([1, 9])),
+            (Symbol::from('B'), HashSet::from([2])),
+        ]);
+        let universe = HashSet::from([1, 2, 3]);
+        assert_eq!(
+            ast.evaluate_set_of(&sets, &universe),
+            Ok(HashSet::from([1, 2, 9]))
+        );
+
+        let ast = AstNode::try_from("A!").unwrap();
+        assert_eq!(
+            ast.evaluate_set_of(&sets, &universe),
+            Ok(HashSet::from([2, 3]))
+        );
+    }
+}
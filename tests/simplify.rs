@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::simplify::{Rule, Step};
+
+    fn simplify(formula: &str) -> String {
+        AstNode::try_from(formula).unwrap().simplify().to_rpn()
+    }
+
+    #[test]
+    fn test_rules() {
+        let cases = [
+            // Idempotence
+            ("AA&", "A"),
+            ("AA|", "A"),
+            ("AA^", "0"),
+            ("AA=", "1"),
+            ("AA>", "1"),
+            // Complementation
+            ("AA!|", "1"),
+            ("AA!&", "0"),
+            ("AA!^", "1"),
+            ("AA!=", "0"),
+            ("AA!>", "A!"),
+            // Absorption
+            ("AAB|&", "A"),
+            ("AB&A|", "A"),
+            // Subsumption
+            ("AB|ABC||&", "AB|"),
+            ("AB&CBA&&|", "AB&"),
+            // Double negation
+            ("A!!", "A"),
+            // Constants
+            ("A1&", "A"),
+            ("A0&", "0"),
+            ("A1|", "1"),
+            ("A0|", "A"),
+            ("A1^", "A!"),
+            ("A0=", "A!"),
+            ("0A>", "1"),
+            ("1A>", "A"),
+            ("A0>", "A!"),
+            ("1!", "0"),
+        ];
+        for (formula, expected) in cases {
+            assert_eq!(simplify(formula), expected, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_fixpoint() {
+        // Removing B & B exposes A & !A, which makes the conjunction false and leaves C
+        assert_eq!(simplify("AA!BB&&&C|"), "C");
+        assert_eq!(simplify("AB|C&AB|C&|!!"), "AB|C&");
+        assert_eq!(simplify("AB&C&A&"), "ABC&&");
+    }
+
+    #[test]
+    fn test_cnf_cleanup() {
+        // The CNF of (A ∧ B) ∨ A repeats literals and clauses
+        let ast = AstNode::try_from("AB&A|").unwrap();
+        assert_eq!(ast.to_cnf().to_rpn(), "AA|BA|&");
+        assert_eq!(ast.to_cnf().simplify().to_rpn(), "A");
+    }
+
+    #[test]
+    fn test_equivalent() {
+        for formula in ["AB|A!B|&C^", "AA&B!!|C0|&", "AB=A!B!=^", "ABA|&C>C!A|>"] {
+            let ast = AstNode::try_from(formula).unwrap();
+            assert!(ast.equivalent(&ast.simplify()).holds(), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let ast = AstNode::try_from("AA&!!").unwrap();
+        let simplification = ast.simplify_with(&Rule::ALL);
+        let step = |rule, before: &str, after: &str| Step {
+            rule,
+            before: AstNode::try_from(before).unwrap(),
+            after: AstNode::try_from(after).unwrap(),
+        };
+        assert_eq!(
+            simplification.trace,
+            vec![
+                step(Rule::Idempotence, "AA&", "A"),
+                step(Rule::DoubleNegation, "A!!", "A"),
+            ]
+        );
+        assert_eq!(simplification.result, AstNode::try_from("A").unwrap());
+    }
+
+    #[test]
+    fn test_rule_selection() {
+        let ast = AstNode::try_from("AA&!!").unwrap();
+        let simplification = ast.simplify_with(&[Rule::DoubleNegation]);
+        assert_eq!(simplification.result.to_rpn(), "AA&");
+        assert_eq!(simplification.trace.len(), 1);
+
+        let simplification = ast.simplify_with(&[]);
+        assert_eq!(simplification.result, ast);
+        assert!(simplification.trace.is_empty());
+    }
+}
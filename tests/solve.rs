@@ -0,0 +1,60 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::boole::solve;
+    use boole::solver::Solution;
+    use boole::symbol::Symbol;
+    use std::collections::HashMap;
+
+    fn model(formula: &str) -> HashMap<Symbol, bool> {
+        match solve(formula) {
+            Solution::Sat(model) => model,
+            Solution::Unsat => panic!("{} should be satisfiable", formula),
+        }
+    }
+
+    #[test]
+    fn test_solve_unsat() {
+        assert_eq!(solve("AA!&"), Solution::Unsat);
+        assert_eq!(solve("AB^AB=&"), Solution::Unsat);
+        assert_eq!(solve("0"), Solution::Unsat);
+    }
+
+    #[test]
+    fn test_solve_forced_model() {
+        let model = model("AB!&");
+        assert!(model[&Symbol::from('A')]);
+        assert!(!model[&Symbol::from('B')]);
+    }
+
+    #[test]
+    fn test_solve_model_covers_variables() {
+        let model = model("AB|C>");
+        assert_eq!(model.len(), 3);
+        let ast = AstNode::try_from("AB|C>").unwrap();
+        assert_eq!(ast.evaluate(&model), Ok(true));
+    }
+
+    #[test]
+    fn test_solve_constant() {
+        assert_eq!(solve("1"), Solution::Sat(HashMap::new()));
+    }
+
+    #[test]
+    fn test_solve_configuration() {
+        // Exactly one of three options, `fast` requires `cache`
+        let ast = AstNode::from_infix(
+            "(fast | safe | cache) & !(fast & safe) & !(fast & cache) & !(safe & cache) \
+             & (fast -> cache) & !safe",
+        )
+        .unwrap();
+        assert_eq!(
+            ast.solve(),
+            Solution::Sat(HashMap::from([
+                (Symbol::new("fast"), false),
+                (Symbol::new("safe"), false),
+                (Symbol::new("cache"), true),
+            ]))
+        );
+    }
+}
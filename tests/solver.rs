@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::{AstNode, Operator};
+    use boole::boole::sat;
+    use boole::cnf::{Cnf, Lit};
+    use boole::solver::{Solution, Solver};
+    use boole::symbol::Symbol;
+    use std::collections::HashMap;
+
+    // Small deterministic generator so the tests do not need a dependency
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: u64) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (self.0 >> 33) % bound
+        }
+    }
+
+    fn random_formula(rng: &mut Lcg, depth: u32) -> AstNode {
+        if depth == 0 || rng.next(4) == 0 {
+            let var = (b'A' + rng.next(4) as u8) as char;
+            return AstNode::Variable(Symbol::from(var));
+        }
+        let op = match rng.next(6) {
+            0 => Operator::And,
+            1 => Operator::Or,
+            2 => Operator::Xor,
+            3 => Operator::Iff,
+            4 => Operator::Implies,
+            _ => return AstNode::Not(Box::new(random_formula(rng, depth - 1))),
+        };
+        AstNode::BinaryOperator(
+            op,
+            Box::new(random_formula(rng, depth - 1)),
+            Box::new(random_formula(rng, depth - 1)),
+        )
+    }
+
+    #[test]
+    fn test_solver_matches_truth_table() {
+        let mut rng = Lcg(42);
+        for _ in 0..500 {
+            let formula = random_formula(&mut rng, 5);
+            let expected = formula.truth_table().iter().any(|(_, result)| *result);
+            assert_eq!(formula.is_satisfiable(), expected, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_solver_pigeonhole() {
+        // 7 pigeons do not fit in 6 holes
+        let (pigeons, holes) = (7, 6);
+        let var = |p: u32, h: u32| p * holes + h;
+        let mut cnf = Cnf::new();
+        for p in 0..pigeons {
+            cnf.add_clause((0..holes).map(|h| Lit::positive(var(p, h))).collect());
+        }
+        for h in 0..holes {
+            for p in 0..pigeons {
+                for q in p + 1..pigeons {
+                    cnf.add_clause(vec![Lit::negative(var(p, h)), Lit::negative(var(q, h))]);
+                }
+            }
+        }
+        assert!(!Solver::from_cnf(&cnf).solve());
+    }
+
+    #[test]
+    fn test_solver_planted_3sat() {
+        // Random 3-SAT instance with 3000 variables satisfied by a hidden assignment
+        let mut rng = Lcg(7);
+        let num_vars = 3000;
+        let hidden: Vec<bool> = (0..num_vars).map(|_| rng.next(2) == 1).collect();
+        let mut cnf = Cnf::new();
+        while cnf.clauses().len() < 9000 {
+            let clause: Vec<Lit> = (0..3)
+                .map(|_| Lit::new(rng.next(num_vars) as u32, rng.next(2) == 1))
+                .collect();
+            if clause
+                .iter()
+                .any(|lit| hidden[lit.var() as usize] != lit.is_negated())
+            {
+                cnf.add_clause(clause);
+            }
+        }
+
+        let mut solver = Solver::from_cnf(&cnf);
+        assert!(solver.solve());
+        let model = solver.model();
+        for clause in cnf.clauses() {
+            assert!(clause
+                .iter()
+                .any(|lit| model[lit.var() as usize] != lit.is_negated()));
+        }
+    }
+
+    #[test]
+    fn test_sat_large_xor_chain() {
+        let formula: String = std::iter::once("{x0}".to_string())
+            .chain((1..3000).map(|i| format!("{{x{}}}^", i)))
+            .collect();
+        assert!(sat(&formula));
+    }
+
+    #[test]
+    fn test_sat_large_implication_chain() {
+        // x0 ∧ (x0 → x1) ∧ ... ∧ (x2998 → x2999) ∧ ¬x2999
+        let mut formula = "{x0}".to_string();
+        for i in 1..3000 {
+            formula += &format!("{{x{}}}{{x{}}}>&", i - 1, i);
+        }
+        formula += "{x2999}!&";
+        assert!(!sat(&formula));
+    }
+
+    #[test]
+    fn test_solve_deep_formula() {
+        // A left-deep chain of 200 000 conjunctions, beyond any recursive traversal
+        let formula = "A".to_string() + &"A&".repeat(200_000);
+        let ast = AstNode::try_from(formula.as_str()).unwrap();
+        assert_eq!(
+            ast.solve(),
+            Solution::Sat(HashMap::from([(Symbol::from('A'), true)]))
+        );
+        let unsat = formula + "A!&";
+        assert!(!sat(&unsat));
+    }
+}
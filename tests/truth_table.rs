@@ -0,0 +1,234 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::{AstNode, Operator};
+    use boole::boole::try_print_truth_table;
+    use boole::error::Error;
+    use boole::symbol::Symbol;
+    use boole::truth_table::TruthTable;
+
+    fn table(formula: &str) -> TruthTable {
+        AstNode::try_from(formula).unwrap().to_truth_table()
+    }
+
+    fn symbols(names: &str) -> Vec<Symbol> {
+        names.chars().map(Symbol::from).collect()
+    }
+
+    const FORMULAS: [&str; 8] = [
+        "AB&!",
+        "AB|C&",
+        "AB>C=",
+        "AB^C^",
+        "AB=!C|",
+        "AB|C|D|",
+        "ABCDEFG&&&&&|",
+        "A1&B0|^",
+    ];
+
+    #[test]
+    fn test_matches_evaluation() {
+        for formula in FORMULAS {
+            let ast = AstNode::try_from(formula).unwrap();
+            let table = ast.to_truth_table();
+            let mut count = 0;
+            for (values, value) in ast.truth_table() {
+                let row = table
+                    .variables()
+                    .iter()
+                    .fold(0, |row, v| row << 1 | values[v] as usize);
+                assert_eq!(table.value(row), value, "{} row {}", formula, row);
+                count += value as u64;
+            }
+            assert_eq!(table.count_ones(), count);
+        }
+    }
+
+    #[test]
+    fn test_layout() {
+        let table = AstNode::try_from("BA>").unwrap().to_truth_table();
+        assert_eq!(table.variables(), [Symbol::from('A'), Symbol::from('B')]);
+        assert_eq!(table.rows(), 4);
+        // Rows AB = 00, 10, 11 are true
+        assert_eq!(table.words(), [0b1101]);
+
+        let constant = AstNode::try_from("1").unwrap().to_truth_table();
+        assert_eq!(constant.rows(), 1);
+        assert_eq!(constant.words(), [1]);
+    }
+
+    #[test]
+    fn test_wide_columns() {
+        // A fills the second word, G alternates and BCDEF sets the top two bits of each word
+        let table = AstNode::try_from("AG|BCDEF&&&&|").unwrap().to_truth_table();
+        assert_eq!(table.words(), [0xEAAA_AAAA_AAAA_AAAA, !0]);
+
+        let table = AstNode::try_from("ABCDEFG&&&&&&").unwrap().to_truth_table();
+        assert_eq!(table.words(), [0, 1 << 63]);
+    }
+
+    #[test]
+    fn test_many_variables() {
+        // Parity of 24 variables is true on half of the rows
+        let formula: String = ('A'..='X').collect::<String>() + &"^".repeat(23);
+        let table = AstNode::try_from(formula.as_str())
+            .unwrap()
+            .to_truth_table();
+        assert_eq!(table.rows(), 1 << 24);
+        assert_eq!(table.count_ones(), 1 << 23);
+        assert!(table.value(1));
+        assert!(!table.value(3));
+    }
+
+    #[test]
+    fn test_operators() {
+        assert_eq!(table("AB&") | table("AB!&"), table("AB0&|"));
+        assert_eq!(&table("AB|") & &table("AB^"), table("AB^"));
+        assert_eq!(!table("AB>"), table("AB!&"));
+        assert_eq!(
+            (table("AB^") ^ table("A")).minterms(),
+            table("BA0&|").minterms()
+        );
+
+        // Tables over different variables are combined over all of them
+        let combined = table("AC&") | table("B");
+        assert_eq!(combined, table("AC&B|"));
+        assert_eq!(combined.variables(), symbols("ABC"));
+    }
+
+    #[test]
+    fn test_minterms_and_maxterms() {
+        let majority = table("AB&AC&|BC&|");
+        assert_eq!(majority.minterms(), [3, 5, 6, 7]);
+        assert_eq!(majority.maxterms(), [0, 1, 2, 4]);
+        assert_eq!(table("0").minterms(), []);
+        assert_eq!(table("AB|!A&").maxterms(), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_cofactor() {
+        let mux = table("SA&S!B&|");
+        assert_eq!(mux.cofactor(Symbol::from('S'), true), table("AB0&|"));
+        assert_eq!(mux.cofactor(Symbol::from('S'), false), table("BA0&|"));
+        assert_eq!(
+            mux.cofactor(Symbol::from('A'), true).variables(),
+            symbols("BS")
+        );
+        assert_eq!(mux.cofactor(Symbol::from('Z'), true), mux);
+
+        // Shannon expansion rebuilds the table
+        let a = Symbol::from('A');
+        let f = table("AB^C|");
+        let expanded = (table("A") & f.cofactor(a, true)) | (!table("A") & f.cofactor(a, false));
+        assert_eq!(expanded, f);
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(table("AB&").to_hex(), "8");
+        assert_eq!(table("AB!&").to_hex(), "4");
+        assert_eq!(table("AB&AC&|BC&|").to_hex(), "e8");
+        assert_eq!(table("A").to_hex(), "2");
+        // The upper half of an odd parity is the complement of the lower one
+        assert_eq!(
+            table("ABCDEFG^^^^^^").to_hex(),
+            "96696996699696696996966996696996"
+        );
+
+        for formula in ["AB^C|", "ABCDEFG&&&&&|", "1"] {
+            let t = table(formula);
+            let parsed = TruthTable::from_hex(t.variables().to_vec(), &t.to_hex()).unwrap();
+            assert_eq!(parsed, t);
+        }
+        let parsed = TruthTable::from_hex(symbols("ABC"), "0xE8").unwrap();
+        assert_eq!(parsed, table("AB&AC&|BC&|"));
+        assert_eq!(TruthTable::from_hex(symbols("ABC"), "0Xe8"), Ok(parsed));
+
+        assert_eq!(
+            TruthTable::from_hex(symbols("ABC"), "e"),
+            Err(Error::HexLength {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            TruthTable::from_hex(symbols("ABC"), "0xeg"),
+            Err(Error::InvalidHex { pos: 3 })
+        );
+        assert_eq!(
+            TruthTable::from_hex(symbols("A"), "4"),
+            Err(Error::InvalidHex { pos: 0 })
+        );
+        // Positions are byte offsets past multibyte characters
+        assert_eq!(
+            TruthTable::from_hex(symbols("ABCD"), "0xé0g"),
+            Err(Error::InvalidHex { pos: 2 })
+        );
+        assert_eq!(
+            TruthTable::from_hex(symbols("ABCDE"), "0xé0g"),
+            Err(Error::HexLength {
+                expected: 8,
+                found: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_to_ast() {
+        assert_eq!(table("AB^").to_ast().to_rpn(), "A!B&AB!&|");
+        assert_eq!(table("AA!&").to_ast(), AstNode::Constant(false));
+        assert_eq!(table("1").to_ast(), AstNode::Constant(true));
+        for formula in FORMULAS {
+            let t = table(formula);
+            assert_eq!(t.to_ast().to_truth_table(), t, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let conjunction = |prefix: char, n: usize| {
+            let formula: String = (0..n)
+                .map(|i| format!("{{{}{}}}", prefix, i))
+                .collect::<String>()
+                + &"&".repeat(n - 1);
+            AstNode::try_from(formula.as_str()).unwrap()
+        };
+
+        let wide = conjunction('x', 25);
+        let too_many = Error::TooManyVariables {
+            variables: 25,
+            limit: TruthTable::MAX_VARIABLES,
+        };
+        assert_eq!(wide.try_to_truth_table(), Err(too_many.clone()));
+        let formula = wide.to_rpn();
+        assert_eq!(try_print_truth_table(&formula), Err(too_many));
+        assert_eq!(
+            TruthTable::try_new(&table("AB&").to_ast(), symbols("A")),
+            Err(Error::UnboundVariable(Symbol::from('B')))
+        );
+        assert_eq!(
+            TruthTable::from_hex(
+                (0..25).map(|i| Symbol::new(&format!("x{}", i))).collect(),
+                "0"
+            ),
+            Err(Error::TooManyVariables {
+                variables: 25,
+                limit: TruthTable::MAX_VARIABLES
+            })
+        );
+
+        // Two disjoint tables of 20 variables would need 2^40 rows together
+        let x = conjunction('x', 20).to_truth_table();
+        let y = conjunction('y', 20).to_truth_table();
+        assert_eq!(
+            x.apply(&y, &Operator::And),
+            Err(Error::TooManyVariables {
+                variables: 40,
+                limit: TruthTable::MAX_VARIABLES
+            })
+        );
+        assert_eq!(
+            table("AB&").apply(&table("BC|"), &Operator::Iff),
+            Ok(table("AB&BC|="))
+        );
+    }
+}
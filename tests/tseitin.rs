@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::AstNode;
+    use boole::solver::Solver;
+    use boole::tseitin::Mode;
+
+    // (A1 ∧ B1) ∨ (A2 ∧ B2) ∨ ... which `to_cnf` expands to 2^n clauses
+    fn sum_of_products(n: usize) -> AstNode {
+        let formula: String = (0..n)
+            .map(|i| format!("{{a{}}}{{b{}}}&", i, i))
+            .collect::<String>()
+            + &"|".repeat(n - 1);
+        AstNode::try_from(formula.as_str()).unwrap()
+    }
+
+    #[test]
+    fn test_tseitin_linear_size() {
+        let ast = sum_of_products(40);
+        let encoding = ast.to_definitional_cnf(Mode::Tseitin);
+        // 40 AND gates and 39 OR gates, 3 clauses each, plus the root
+        assert_eq!(encoding.definitions.len(), 79);
+        assert_eq!(encoding.cnf.clauses().len(), 79 * 3 + 1);
+        assert_eq!(encoding.inputs.len(), 80);
+        assert_eq!(encoding.cnf.num_vars(), 159);
+    }
+
+    #[test]
+    fn test_plaisted_greenbaum_smaller() {
+        let ast = sum_of_products(40);
+        let tseitin = ast.to_definitional_cnf(Mode::Tseitin);
+        let pg = ast.to_definitional_cnf(Mode::PlaistedGreenbaum);
+        // Only the `x → f` direction is needed for positive occurrences
+        assert_eq!(pg.cnf.clauses().len(), 40 * 2 + 39 + 1);
+        assert!(pg.cnf.clauses().len() < tseitin.cnf.clauses().len());
+    }
+
+    #[test]
+    fn test_definitions() {
+        let ast = AstNode::try_from("AB&C|!").unwrap();
+        let encoding = ast.to_definitional_cnf(Mode::Tseitin);
+        let definitions: Vec<(u32, String)> = encoding
+            .definitions
+            .iter()
+            .map(|(var, node)| (*var, node.to_rpn()))
+            .collect();
+        assert_eq!(
+            definitions,
+            vec![(2, "AB&".to_string()), (4, "AB&C|".to_string())]
+        );
+        assert_eq!(encoding.inputs.len(), 3);
+    }
+
+    #[test]
+    fn test_definitional_equisatisfiable() {
+        for formula in ["AB^C=A>", "AB=AB^&", "AB>!A&", "A!A|!", "AB&1|C0>&"] {
+            let ast = AstNode::try_from(formula).unwrap();
+            let expected = ast.truth_table().iter().any(|(_, result)| *result);
+            for mode in [Mode::Tseitin, Mode::PlaistedGreenbaum] {
+                let encoding = ast.to_definitional_cnf(mode);
+                assert_eq!(
+                    Solver::from_cnf(&encoding.cnf).solve(),
+                    expected,
+                    "{} {:?}",
+                    formula,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_tseitin_definitions_hold_in_models() {
+        let ast = AstNode::try_from("AB^C&A!|").unwrap();
+        let encoding = ast.to_definitional_cnf(Mode::Tseitin);
+        let mut solver = Solver::from_cnf(&encoding.cnf);
+        assert!(solver.solve());
+        let model = solver.model();
+        let values = encoding
+            .inputs
+            .iter()
+            .map(|(&name, &var)| (name, model[var as usize]))
+            .collect();
+        for (var, node) in &encoding.definitions {
+            assert_eq!(node.evaluate(&values), Ok(model[*var as usize]));
+        }
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use boole::ast::{AstNode, Operator};
+    use boole::boole::{conjunctive_normal_form, negation_normal_form, sat};
+    use boole::error::ParseError;
+    use boole::symbol::Symbol;
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn test_symbol_interning() {
+        assert_eq!(Symbol::new("enable"), Symbol::from("enable"));
+        assert_ne!(Symbol::new("x_1"), Symbol::new("x_12"));
+        assert_eq!(Symbol::from('A').as_str(), "A");
+        assert!(Symbol::new("a") > Symbol::new("B"));
+    }
+
+    #[test]
+    fn test_rpn_braced_names() {
+        let ast = AstNode::try_from("{x_12}{enable}&{bus[3]}|").unwrap();
+        let expected: HashSet<Symbol> = ["x_12", "enable", "bus[3]"]
+            .into_iter()
+            .map(Symbol::new)
+            .collect();
+        assert_eq!(ast.get_variables(), expected);
+        assert_eq!(ast.to_rpn(), "{x_12}{enable}&{bus[3]}|");
+    }
+
+    #[test]
+    fn test_rpn_whitespace() {
+        let ast = AstNode::try_from("A {b} & C |").unwrap();
+        assert_eq!(ast.to_rpn(), "A{b}&C|");
+    }
+
+    #[test]
+    fn test_rpn_escaped_names() {
+        let name = Symbol::new("f}{x\\");
+        let ast = AstNode::BinaryOperator(
+            Operator::And,
+            Box::new(AstNode::Variable(name)),
+            Box::new(AstNode::Variable(Symbol::new("}"))),
+        );
+        let rpn = ast.to_rpn();
+        assert_eq!(rpn, "{f\\}\\{x\\\\}{\\}}&");
+        assert_eq!(AstNode::try_from(rpn.as_str()), Ok(ast));
+    }
+
+    #[test]
+    fn test_rpn_invalid_names() {
+        assert_eq!(
+            AstNode::try_from("A{}&").unwrap_err(),
+            ParseError::InvalidName { pos: 1 }
+        );
+        assert_eq!(
+            AstNode::try_from("A{bc&").unwrap_err(),
+            ParseError::InvalidName { pos: 1 }
+        );
+    }
+
+    #[test]
+    fn test_infix_identifiers() {
+        let ast = AstNode::from_infix("enable & (x_12 | !bus[3])").unwrap();
+        assert_eq!(ast.to_rpn(), "{enable}{x_12}{bus[3]}!|&");
+        assert_eq!(
+            AstNode::from_infix("bus[x]").unwrap_err(),
+            ParseError::InvalidName { pos: 0 }
+        );
+    }
+
+    #[test]
+    fn test_named_evaluate() {
+        let ast = AstNode::from_infix("enable -> ready").unwrap();
+        let vars = HashMap::from([(Symbol::new("enable"), true), (Symbol::new("ready"), false)]);
+        assert_eq!(ast.evaluate(&vars), Ok(false));
+        assert_eq!(ast.truth_table().len(), 4);
+    }
+
+    #[test]
+    fn test_named_normal_forms() {
+        assert_eq!(negation_normal_form("{up}{down}&!"), "{up}!{down}!|");
+        assert_eq!(conjunctive_normal_form("{a}{b}{c}&|"), "{a}{b}|{a}{c}|&");
+        assert!(!sat("{go}{go}!&"));
+    }
+
+    #[test]
+    fn test_named_evaluate_set() {
+        let ast = AstNode::try_from("{even}{small}&").unwrap();
+        let sets = HashMap::from([
+            (Symbol::new("even"), vec![0, 2, 4]),
+            (Symbol::new("small"), vec![0, 1, 2]),
+        ]);
+        let universe = HashSet::from([0, 1, 2, 3, 4]);
+        let mut result = ast.evaluate_set(sets, universe).unwrap();
+        result.sort();
+        assert_eq!(result, vec![0, 2]);
+    }
+}